@@ -1,8 +1,54 @@
 extern crate winres;
 
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// IEEE MA-L 등록 목록을 줄인 MAC 제조사 목록 (<6자리 hex><TAB><제조사> 형식, examples/compact_oui.rs로 생성)
+const OUI_TABLE: &str = "resources/oui.txt";
+
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=resources.rc");
+    println!("cargo:rerun-if-changed=resources/app-icon.ico");
+    println!("cargo:rerun-if-changed=resources/tray-icon.ico");
+    println!("cargo:rerun-if-changed={}", OUI_TABLE);
+
+    generate_oui_table();
+
     let mut res = winres::WindowsResource::new();
     res.set_icon("resources/app-icon.ico");
     res.set_resource_file("resources.rc");
     res.compile().expect("Failed to compile resources");
-}
\ No newline at end of file
+}
+
+// 저장소의 제조사 목록을 정렬/검증하여 OUT_DIR/oui.txt에 기록 (module::oui에서 include_str!로 포함)
+// 네트워크나 빌드 환경과 관계없이 항상 같은 목록이 포함되며, 잘못된 줄이 있으면 빌드를 중단함
+fn generate_oui_table() {
+    let content = fs::read_to_string(OUI_TABLE).expect("Failed to read OUI table");
+    let mut table = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (prefix, vendor) = line.split_once('\t')
+            .unwrap_or_else(|| panic!("{}:{}: expected <prefix><TAB><vendor>", OUI_TABLE, index + 1));
+        let prefix = prefix.trim().to_uppercase();
+        if prefix.len() != 6 || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            panic!("{}:{}: invalid OUI prefix: {}", OUI_TABLE, index + 1, prefix);
+        }
+        table.insert(prefix, vendor.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    let mut output = String::with_capacity(table.len() * 32);
+    for (prefix, vendor) in &table {
+        output.push_str(prefix);
+        output.push('\t');
+        output.push_str(vendor);
+        output.push('\n');
+    }
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is not set")).join("oui.txt");
+    fs::write(out, output).expect("Failed to write OUI table");
+}
@@ -0,0 +1,90 @@
+// IEEE MA-L 등록 목록(oui.csv)을 resources/oui.txt 형식(<6자리 hex><TAB><제조사>)으로 줄이는 도구
+// 주소 등 불필요한 항목은 제외하고 접두사 순으로 정렬하므로 같은 원본에서는 항상 같은 결과가 나옴
+//   curl -o oui.csv https://standards-oui.ieee.org/oui/oui.csv
+//   cargo run --example compact_oui -- oui.csv resources/oui.txt
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+const HEADER: &str = "\
+# MAC vendor prefixes embedded at build time (see build.rs).
+# Compacted from the IEEE MA-L registry (https://standards-oui.ieee.org/oui/oui.csv) with:
+#   cargo run --example compact_oui -- oui.csv resources/oui.txt
+# Format: <6 hex digits><TAB><vendor>. Lines starting with # are ignored.
+";
+
+// Registry,Assignment,Organization Name,Organization Address
+fn compact_ieee_csv(csv: &str) -> BTreeMap<String, String> {
+    csv.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = split_csv_line(line);
+            let prefix = fields.get(1)?.trim().to_uppercase();
+            let vendor = collapse_whitespace(fields.get(2)?);
+            (prefix.len() == 6 && prefix.chars().all(|c| c.is_ascii_hexdigit()) && !vendor.is_empty())
+                .then_some((prefix, vendor))
+        })
+        .collect()
+}
+
+// 큰따옴표로 감싼 필드(쉼표, "" 이스케이프 포함)를 지원하는 CSV 한 줄 분리
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn run(input: &str, output: &str) -> Result<usize, String> {
+    let csv = fs::read_to_string(input).map_err(|e| format!("Failed to read {}: {}", input, e))?;
+    let table = compact_ieee_csv(&csv);
+    if table.is_empty() {
+        return Err(format!("No MA-L assignments found in {}", input));
+    }
+
+    let mut content = String::from(HEADER);
+    for (prefix, vendor) in &table {
+        content.push_str(prefix);
+        content.push('\t');
+        content.push_str(vendor);
+        content.push('\n');
+    }
+    fs::write(output, content).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    Ok(table.len())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [input, output] = args.as_slice() else {
+        eprintln!("Usage: compact_oui <oui.csv> <resources/oui.txt>");
+        return ExitCode::FAILURE;
+    };
+    match run(input, output) {
+        Ok(count) => {
+            println!("Wrote {} prefixes to {}", count, output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// IPMI 드라이버 확인용 ipmitool 대체 프로그램 (BMC 없이 chassis power 명령만 흉내냄)
+// cargo build --example mock_ipmitool 후 Rocket.toml의 [ipmi] ipmitool을 target/debug/examples/mock_ipmitool로,
+// 장치의 power_config 계정을 admin/password로 지정 (전원 상태는 임시 디렉터리의 파일에 보관)
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USERNAME: &str = "admin";
+const PASSWORD: &str = "password";
+
+fn state_file() -> PathBuf {
+    env::temp_dir().join("mock_ipmitool_power")
+}
+
+fn is_on() -> bool {
+    fs::read_to_string(state_file()).map(|state| state.trim() == "on").unwrap_or(false)
+}
+
+fn set_power(on: bool) -> Result<(), String> {
+    fs::write(state_file(), if on { "on" } else { "off" }).map_err(|e| format!("Failed to save power state: {}", e))
+}
+
+// -I lanplus -H <host> -p <port> -U <user> -E -N <secs> -R <count> chassis power <command>
+fn run(args: &[String]) -> Result<String, String> {
+    let mut user = None;
+    let mut password_from_env = false;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-I" | "-H" | "-p" | "-N" | "-R" => {
+                iter.next().ok_or(format!("Missing value for {}", arg))?;
+            }
+            "-U" => user = iter.next().cloned(),
+            "-E" => password_from_env = true,
+            _ => rest.push(arg.as_str()),
+        }
+    }
+
+    let password = if password_from_env { env::var("IPMI_PASSWORD").ok() } else { None };
+    if user.as_deref() != Some(USERNAME) || password.as_deref() != Some(PASSWORD) {
+        return Err("Error: Unable to establish IPMI v2 / RMCP+ session".to_string());
+    }
+
+    match rest.as_slice() {
+        ["chassis", "power", "status"] => Ok(format!("Chassis Power is {}", if is_on() { "on" } else { "off" })),
+        ["chassis", "power", "on"] => set_power(true).map(|_| "Chassis Power Control: Up/On".to_string()),
+        ["chassis", "power", "off"] => set_power(false).map(|_| "Chassis Power Control: Down/Off".to_string()),
+        ["chassis", "power", "soft"] => set_power(false).map(|_| "Chassis Power Control: Soft".to_string()),
+        _ => Err(format!("Unsupported command: {}", rest.join(" "))),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
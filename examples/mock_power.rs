@@ -0,0 +1,57 @@
+// 전원 드라이버 확인용 모의 스마트 플러그 서버 (Tasmota, Shelly Gen1 HTTP API)
+// cargo run --example mock_power 후 장치의 power_config.url을 http://127.0.0.1:8001로 지정
+#[macro_use]
+extern crate rocket;
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+use rocket::serde::json::{json, Json, Value};
+use rocket::{Config, State};
+
+const MOCK_PORT: u16 = 8001;
+
+// 채널 번호별 전원 상태
+#[derive(Default)]
+struct Relays(Mutex<HashMap<u8, bool>>);
+
+impl Relays {
+    fn switch(&self, relay: u8, turn: Option<&str>) -> Option<bool> {
+        let mut relays = self.0.lock().unwrap();
+        let state = relays.entry(relay).or_insert(false);
+        match turn.map(str::to_lowercase).as_deref() {
+            None => {}
+            Some("on") | Some("1") => *state = true,
+            Some("off") | Some("0") => *state = false,
+            Some("toggle") | Some("2") => *state = !*state,
+            Some(_) => return None,
+        }
+        Some(*state)
+    }
+}
+
+// Tasmota: /cm?cmnd=Power1%20On -> {"POWER1":"ON"}
+#[get("/cm?<cmnd>")]
+fn tasmota(relays: &State<Relays>, cmnd: &str) -> Option<Json<Value>> {
+    let mut parts = cmnd.split_whitespace();
+    let power = parts.next()?.to_uppercase();
+    let relay = power.strip_prefix("POWER")?;
+    let relay = if relay.is_empty() { 1 } else { relay.parse().ok()? };
+    let state = relays.switch(relay, parts.next())?;
+    Some(Json(json!({ power: if state { "ON" } else { "OFF" } })))
+}
+
+// Shelly Gen1: /relay/0?turn=on -> {"ison":true}
+#[get("/relay/<relay>?<turn>")]
+fn shelly(relays: &State<Relays>, relay: u8, turn: Option<&str>) -> Option<Json<Value>> {
+    let state = relays.switch(relay, turn)?;
+    Some(Json(json!({ "ison": state, "has_timer": false, "source": "http" })))
+}
+
+#[launch]
+fn rocket() -> _ {
+    let config = Config { port: MOCK_PORT, address: Ipv4Addr::LOCALHOST.into(), ..Config::debug_default() };
+    rocket::custom(config)
+        .manage(Relays::default())
+        .mount("/", routes![tasmota, shelly])
+}
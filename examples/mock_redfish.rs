@@ -0,0 +1,85 @@
+// Redfish 드라이버 확인용 모의 BMC 서버
+// cargo run --example mock_redfish 후 장치의 bmc_url을 http://127.0.0.1:8002, 계정을 admin/password로 지정
+#[macro_use]
+extern crate rocket;
+
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::json::{json, Json, Value};
+use rocket::{Config, State};
+
+const MOCK_PORT: u16 = 8002;
+const SYSTEM_ID: &str = "1";
+const USERNAME: &str = "admin";
+const PASSWORD: &str = "password";
+
+// 시스템 전원 상태 (On, Off)
+struct PowerState(Mutex<&'static str>);
+
+// HTTP Basic 인증 확인
+struct BasicAuth;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for BasicAuth {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let expected = format!("Basic {}", STANDARD.encode(format!("{}:{}", USERNAME, PASSWORD)));
+        match req.headers().get_one("Authorization") {
+            Some(value) if value == expected => Outcome::Success(BasicAuth),
+            _ => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[get("/redfish/v1/Systems")]
+fn systems(_auth: BasicAuth) -> Json<Value> {
+    Json(json!({
+        "@odata.id": "/redfish/v1/Systems",
+        "Members@odata.count": 1,
+        "Members": [{ "@odata.id": format!("/redfish/v1/Systems/{}", SYSTEM_ID) }],
+    }))
+}
+
+#[get("/redfish/v1/Systems/<id>")]
+fn system(_auth: BasicAuth, state: &State<PowerState>, id: &str) -> Option<Json<Value>> {
+    (id == SYSTEM_ID).then(|| Json(json!({
+        "@odata.id": format!("/redfish/v1/Systems/{}", SYSTEM_ID),
+        "Id": SYSTEM_ID,
+        "PowerState": *state.0.lock().unwrap(),
+        "Actions": {
+            "#ComputerSystem.Reset": {
+                "target": format!("/redfish/v1/Systems/{}/Actions/ComputerSystem.Reset", SYSTEM_ID),
+                "ResetType@Redfish.AllowableValues": ["On", "ForceOff", "GracefulShutdown", "PushPowerButton", "ForceRestart", "GracefulRestart"],
+            }
+        },
+    })))
+}
+
+#[post("/redfish/v1/Systems/<id>/Actions/ComputerSystem.Reset", data = "<body>")]
+fn reset(_auth: BasicAuth, state: &State<PowerState>, id: &str, body: Json<Value>) -> Status {
+    if id != SYSTEM_ID {
+        return Status::NotFound;
+    }
+    let mut power = state.0.lock().unwrap();
+    match body["ResetType"].as_str() {
+        Some("On") => *power = "On",
+        Some("ForceOff") | Some("GracefulShutdown") | Some("PushPowerButton") => *power = "Off",
+        Some("ForceRestart") | Some("GracefulRestart") if *power == "On" => {}
+        Some("ForceRestart") | Some("GracefulRestart") => return Status::Conflict,
+        _ => return Status::BadRequest,
+    }
+    Status::NoContent
+}
+
+#[launch]
+fn rocket() -> _ {
+    let config = Config { port: MOCK_PORT, address: Ipv4Addr::LOCALHOST.into(), ..Config::debug_default() };
+    rocket::custom(config)
+        .manage(PowerState(Mutex::new("Off")))
+        .mount("/", routes![systems, system, reset])
+}
@@ -1,15 +1,360 @@
 interface PostDeviceReq {
   id: number|null;
   name: string;
-  mac: string;
+  mac?: string|null;
+  allow_local_mac?: boolean|null;
   ip: string;
+  broadcast?: string|null;
+  netmask?: string|null;
+  port?: number|null;
+  interface?: string|null;
+  secureon?: string|null;
+  transport?: 'udp'|'ethernet'|'both'|null;
+  vlan_id?: number|null;
+  burst_count?: number|null;
+  burst_interval_ms?: number|null;
+  wake_targets?: ('broadcast'|'subnet'|'unicast')[]|null;
+  verify_port?: number|null;
+  ssh_user?: string|null;
+  ssh_port?: number|null;
+  ssh_password?: string|null;
+  ssh_private_key?: string|null;
+  ssh_command?: string|null;
+  reset_ssh_host_key?: boolean|null;
+  sleep_on_lan?: boolean|null;
+  power_driver?: PowerDriver|null;
+  power_config?: Record<string, unknown>|null;
+  bmc_url?: string|null;
+  bmc_user?: string|null;
+  bmc_password?: string|null;
+  bmc_system_id?: string|null;
+  bmc_insecure?: boolean|null;
+  group_id?: number|null;
+  tags?: string[]|null;
+  depends_on?: number[]|null;
+  probes?: PostProbeReq[]|null;
+  actions?: PostActionReq[]|null;
+}
+
+type PowerDriver = 'wol'|'redfish'|'ipmi'|'smart_plug';
+type PowerState = 'on'|'off'|'unknown';
+
+interface PowerReport {
+  device_id: number;
+  driver: PowerDriver;
+  operation: 'on'|'off';
+  state: PowerState;
+  detail: unknown;
+}
+
+interface PowerStatusRes {
+  device_id: number;
+  driver: PowerDriver;
+  state: PowerState;
+}
+
+type ActionMethod = 'GET'|'POST'|'PUT'|'PATCH'|'DELETE';
+
+interface PostActionReq {
+  id?: number|null;
+  name: string;
+  method: ActionMethod;
+  url: string;
+  headers?: Record<string, string>|null;
+  body?: string|null;
+  expected_status?: number|null;
+  timeout_ms?: number|null;
+}
+
+interface DeviceAction {
+  id: number;
+  name: string;
+  method: ActionMethod;
+  url: string;
+  header_names: string[];
+  body: string|null;
+  expected_status: number|null;
+  timeout_ms: number;
+}
+
+interface ActionResult {
+  device_id: number;
+  action: string;
+  method: ActionMethod;
+  url: string;
+  success: boolean;
+  status_code: number|null;
+  latency_ms: number;
+  body: string|null;
+  error: string|null;
+}
+
+interface ActionLog {
+  id: number;
+  device_id: number;
+  action_name: string;
+  executed_at: string;
+  success: boolean;
+  status_code: number|null;
+  latency_ms: number|null;
+  error: string|null;
+}
+
+interface PostProbeReq {
+  id?: number|null;
+  name: string;
+  kind: 'tcp'|'http';
+  port?: number|null;
+  url?: string|null;
+  expected_status?: number|null;
+  body_contains?: string|null;
+  interval_secs?: number|null;
+  timeout_ms?: number|null;
+}
+
+interface Probe {
+  id: number;
+  device_id: number;
+  name: string;
+  kind: 'tcp'|'http';
+  port: number|null;
+  url: string|null;
+  expected_status: number|null;
+  body_contains: string|null;
+  interval_secs: number;
+  timeout_ms: number;
+  status: 'unknown'|'up'|'down';
+  last_checked: string|null;
+  last_latency_ms: number|null;
+  last_error: string|null;
 }
 
 interface GetDeviceRes {
   id: number;
   name: string;
   mac: string;
+  vendor: string|null;
   ip: string;
+  resolved_ip: string|null;
   order_num: number;
+  broadcast: string|null;
+  netmask: string|null;
+  port: number;
+  interface: string|null;
+  has_secureon: boolean;
+  transport: 'udp'|'ethernet'|'both';
+  vlan_id: number|null;
+  burst_count: number|null;
+  burst_interval_ms: number|null;
+  wake_targets: ('broadcast'|'subnet'|'unicast')[]|null;
+  verify_port: number|null;
+  ssh_user: string|null;
+  ssh_port: number|null;
+  has_ssh_password: boolean;
+  has_ssh_private_key: boolean;
+  ssh_command: string|null;
+  ssh_host_key: string|null;
+  sleep_on_lan: boolean;
+  power_driver: PowerDriver;
+  power_config: Record<string, unknown>|null;
+  has_power_password: boolean;
+  bmc_url: string|null;
+  bmc_user: string|null;
+  has_bmc_password: boolean;
+  bmc_system_id: string|null;
+  bmc_insecure: boolean;
+  group_id: number|null;
+  tags: string[];
+  depends_on: number[];
+  status: 'unknown'|'online'|'offline';
+  last_seen: string|null;
+  probes: Probe[];
+  actions: DeviceAction[];
+}
+
+interface DeviceGroup {
+  id: number;
+  name: string;
+  order_num: number;
+  wake_stagger_ms: number|null;
+}
+
+interface PostGroupReq {
+  id: number|null;
+  name: string;
+  wake_stagger_ms?: number|null;
+}
+
+interface GroupWakeRes {
+  group_id: number;
+  device_ids: number[];
+  stagger_ms: number;
+}
+
+type SceneStepKind = 'wake'|'wait'|'wait_online'|'run_action'|'shutdown'|'notify';
+type SceneRunStatus = 'running'|'succeeded'|'failed';
+
+interface PostSceneStepReq {
+  kind: SceneStepKind;
+  device_id?: number|null;
+  seconds?: number|null;
+  action_name?: string|null;
+  message?: string|null;
+  url?: string|null;
+}
+
+interface PostSceneReq {
+  id: number|null;
+  name: string;
+  steps: PostSceneStepReq[];
 }
 
+interface SceneStep {
+  id: number;
+  scene_id: number;
+  step_order: number;
+  kind: SceneStepKind;
+  device_id: number|null;
+  seconds: number|null;
+  action_name: string|null;
+  message: string|null;
+  url: string|null;
+}
+
+interface Scene {
+  id: number;
+  name: string;
+  steps: SceneStep[];
+}
+
+interface SceneRun {
+  id: number;
+  scene_id: number;
+  status: SceneRunStatus;
+  started_at: string;
+  finished_at: string|null;
+  error: string|null;
+}
+
+interface SceneRunLog {
+  id: number;
+  run_id: number;
+  step_order: number;
+  kind: SceneStepKind;
+  device_id: number|null;
+  success: boolean;
+  message: string|null;
+  logged_at: string;
+}
+
+interface SceneRunRes extends SceneRun {
+  logs: SceneRunLog[];
+}
+
+interface WakeAttempt {
+  round: number;
+  target: 'broadcast'|'subnet'|'unicast'|'ethernet';
+  address: string|null;
+  port: number|null;
+  success: boolean;
+  error: string|null;
+}
+
+interface WakeReport {
+  device_id: number;
+  sent: number;
+  failed: number;
+  attempts: WakeAttempt[];
+}
+
+interface WakeStep {
+  device_id: number;
+  name: string;
+  dependency: boolean;
+  status: 'already_online'|'online'|'timeout'|'sent'|'failed';
+  report: PowerReport|null;
+  error: string|null;
+  elapsed_ms: number;
+}
+
+interface WakeJob {
+  id: string;
+  device_id: number;
+  status: 'waking'|'verifying'|'online'|'failed';
+  started_at: string;
+  finished_at: string|null;
+  elapsed_secs: number;
+  dependencies: WakeStep[];
+  report: PowerReport|null;
+  message: string|null;
+}
+
+interface WakeStart {
+  device_id: number;
+  plan: number[];
+  report: PowerReport|null;
+  job: WakeJob|null;
+}
+
+interface SshCommandResult {
+  device_id: number;
+  command: string;
+  exit_code: number|null;
+  stdout: string;
+  stderr: string;
+  host_key: string|null;
+  host_key_trusted: boolean;
+}
+
+type DeviceEvent =
+  | { type: 'device_created'|'device_updated'|'device_deleted'|'device_moved'; device_id: number }
+  | { type: 'wake'|'sleep'; report: WakeReport }
+  | { type: 'wake_job'; job: WakeJob }
+  | { type: 'action_run'; device_id: number; action: string; success: boolean }
+  | { type: 'power'; device_id: number; operation: 'on'|'off'; state: PowerState }
+  | { type: 'shutdown'; device_id: number; exit_code: number|null }
+  | { type: 'group_wake'; group_id: number; device_id: number; success: boolean; error: string|null }
+  | { type: 'scene_run'; scene_id: number; run_id: number; status: SceneRunStatus }
+  | { type: 'scene_notification'; scene_id: number; run_id: number; message: string }
+  | { type: 'status_changed'; device_id: number; status: 'unknown'|'online'|'offline' }
+  | { type: 'probe_changed'; device_id: number; probe_id: number; status: 'up'|'down' };
+
+type WsCommand =
+  | { id?: string|number; command: 'subscribe'|'unsubscribe' }
+  | { id?: string|number; command: 'wake'; device_id: number }
+  | { id?: string|number; command: 'move'; device_id: number; direction: 'up'|'down' }
+  | { id?: string|number; command: 'refresh_status'; device_id?: number|null };
+
+type WsMessage =
+  | { type: 'ack'; id: string|number|null; ok: boolean; result: unknown; error: string|null }
+  | { type: 'event'; event: DeviceEvent }
+  | { type: 'resync' };
+
+interface NeighborEntry {
+  ip: string;
+  mac: string;
+  interface: string|null;
+}
+
+interface DiscoveredHost {
+  ip: string;
+  mac: string|null;
+  hostname: string|null;
+  vendor: string|null;
+  interface: string|null;
+}
+
+interface ScanReq {
+  cidr?: string|null;
+}
+
+interface ImportReq {
+  devices: { name: string; mac: string; ip: string; allow_local_mac?: boolean|null }[];
+}
+
+interface MacInfo {
+  mac: string;
+  vendor: string|null;
+  locally_administered: boolean;
+  multicast: boolean;
+}
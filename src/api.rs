@@ -1,24 +1,71 @@
-use validator::Validate;
-use crate::error::SystemError;
+use rocket::serde::{Deserialize, Deserializer};
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+use crate::error::{PredefinedApiError, SystemError};
 
 pub mod user_api;
 pub mod catcher;
 pub mod device_api;
+pub mod admin_api;
+pub mod event_api;
+pub mod ws_api;
+pub mod discovery_api;
+pub mod group_api;
+pub mod scene_api;
 
 /// 밸리데이션을 수행하고 에러 메시지를 포맷팅하는 헬퍼 함수
 fn validate_request<T: Validate>(data: &T) -> Result<(), SystemError> {
     if let Err(errors) = data.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(field, errors)| {
-                errors.iter().map(move |e| {
-                    format!("{}: {}", field, e.message.as_ref().unwrap_or(&std::borrow::Cow::Borrowed("유효하지 않은 값")))
-                })
-            })
-            .collect();
+        let mut error_messages = Vec::new();
+        collect_error_messages("", &errors, &mut error_messages);
         return Err(SystemError::APIError(400, 0, error_messages.join(", ")));
     }
     Ok(())
 }
 
+/// 중첩된 구조체/목록의 에러까지 "필드.하위필드" 형태의 경로로 펼쳐서 수집
+fn collect_error_messages(prefix: &str, errors: &ValidationErrors, messages: &mut Vec<String>) {
+    for (field, kind) in errors.errors() {
+        // 구조체 단위(schema) 검증 에러는 __all__ 키로 들어옴
+        let path = match (prefix.is_empty(), field.as_ref()) {
+            (true, "__all__") => String::new(),
+            (false, "__all__") => prefix.to_string(),
+            (true, field) => field.to_string(),
+            (false, field) => format!("{}.{}", prefix, field),
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                messages.extend(errors.iter().map(|e| {
+                    let message = e.message.as_ref().unwrap_or(&std::borrow::Cow::Borrowed("유효하지 않은 값"));
+                    if path.is_empty() { message.to_string() } else { format!("{}: {}", path, message) }
+                }));
+            }
+            ValidationErrorsKind::Struct(errors) => collect_error_messages(&path, errors, messages),
+            ValidationErrorsKind::List(errors) => {
+                for (index, errors) in errors {
+                    collect_error_messages(&format!("{}[{}]", path, index), errors, messages);
+                }
+            }
+        }
+    }
+}
+
+/// 유니크 제약 위반을 Duplicated 에러로 변환하는 헬퍼 함수
+fn map_duplicated(error: sqlx::Error) -> SystemError {
+    match error.as_database_error() {
+        Some(e) if e.is_unique_violation() => PredefinedApiError::Duplicated.get(),
+        _ => SystemError::Sql(error),
+    }
+}
+
+/// 필드 미지정(None)과 null(Some(None))을 구분하는 역직렬화 헬퍼 함수 (#[serde(default)]와 함께 사용)
+fn deserialize_nullable<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<T>>, D::Error> {
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 공백뿐인 선택 입력값을 None으로 정리하는 헬퍼 함수
+fn none_if_empty(value: &Option<String>) -> Option<String> {
+    value.as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,21 @@
+use rocket::serde::json::Json;
+use rocket::http::Status;
+use rocket::State;
+use crate::error::SystemError;
+use crate::module::auth::AuthUser;
+use crate::module::transport::{CapturedPacket, SharedTransport};
+
+// 드라이런 모드에서 기록된 매직패킷 목록
+#[get("/admin/packets")]
+pub async fn get_captured_packets(_auth: AuthUser, transport: &State<SharedTransport>)
+    -> Result<Json<Vec<CapturedPacket>>, SystemError> {
+    let packets = transport.captured()
+        .ok_or(SystemError::APIError(400, 0, "Packet capture is only available in dry-run mode".to_string()))?;
+    Ok(Json(packets))
+}
+
+#[delete("/admin/packets")]
+pub async fn clear_captured_packets(_auth: AuthUser, transport: &State<SharedTransport>) -> Result<Status, SystemError> {
+    transport.clear_captured();
+    Ok(Status::Ok)
+}
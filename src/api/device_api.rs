@@ -1,131 +1,901 @@
 use rocket::serde::{json::Json, Deserialize, Serialize};
 use rocket::http::Status;
+use rocket::response::status::Accepted;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use rocket::futures::future::join_all;
+use rocket::State;
 use serde_json::json;
 use serde_json::ser::CharEscape::CarriageReturn;
 use lazy_static::lazy_static;
 use validator::Validate;
 use std::net::UdpSocket;
-use crate::api::validate_request;
+use sqlx::SqliteConnection;
+use crate::api::{map_duplicated, none_if_empty, validate_request};
 use crate::module::auth::AuthUser;
 use crate::db::Db;
 use crate::db::device::{Device, DeviceOperations, MoveDirection};
+use crate::db::action::{ActionLog, ActionLogOperations, DeviceAction, DeviceActionOperations};
+use crate::db::group::{DeviceGroup, GroupOperations};
+use crate::db::tag::{DeviceTag, TagOperations};
+use crate::db::dependency::{DependencyOperations, DeviceDependency};
+use crate::db::scene::{SceneStep, SceneStepOperations};
+use crate::db::probe::{Probe, ProbeOperations};
 use crate::error::{PredefinedApiError, SystemError};
-use crate::module::magic_packet::send_magic_packet;
+use crate::config::{CryptoConfig, SshConfig, WolConfig};
+use crate::module::action::{decrypt_headers, is_valid_header, run_and_log_action, ActionResult, ACTION_METHODS};
+use crate::module::crypto::encrypt;
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::host::{resolve_host, resolve_host_cached, Host};
+use crate::module::neighbor::{resolve_mac, NeighborEntry};
+use crate::module::oui::{lookup_vendor, mac_info, MacInfo};
+use crate::module::power::{driver_for, public_config, seal_config, PowerDriverKind, PowerOperation,
+                           PowerReport, PowerResources, PowerState};
+use crate::module::ssh::{is_valid_private_key, power_off, SshCommandResult};
+use crate::module::transport::SharedTransport;
+use crate::module::magic_packet::{normalize_mac_address, parse_mac_address, parse_secureon_password, WakeTransport, DEFAULT_WOL_PORT};
+use crate::module::wake::{sleep, WakeReport, WakeTarget, MAX_BURST_COUNT, MAX_BURST_INTERVAL_MS};
+use crate::module::wake_job::{start_wake, start_wake_job, WakeJob, WakeJobs, WakeStart};
+use crate::module::wake_plan::find_cycle;
 
-// MAC 주소 정규식 (일반적인 형식: AA:BB:CC:DD:EE:FF 또는 AA-BB-CC-DD-EE-FF)
-lazy_static::lazy_static! {
-    static ref MAC_ADDRESS_REGEX: regex::Regex = regex::Regex::new(
-        r"^([0-9A-Fa-f]{2}[:-]){5}([0-9A-Fa-f]{2})$"
-    ).unwrap();
-}
+// 헬스 체크 기본값
+const MIN_PROBE_INTERVAL_SECS: u32 = 5;
+const DEFAULT_PROBE_INTERVAL_SECS: u32 = 60;
+const DEFAULT_PROBE_TIMEOUT_MS: u32 = 2000;
+// HTTP 동작 기본값
+const DEFAULT_ACTION_TIMEOUT_MS: u32 = 10_000;
+const DEFAULT_ACTION_LOG_LIMIT: i64 = 50;
+// 태그 최대 길이
+const MAX_TAG_LENGTH: usize = 64;
 
-// MAC 주소 검증 함수
-fn validate_mac_address(mac: &str) -> Result<(), validator::ValidationError> {
-    if MAC_ADDRESS_REGEX.is_match(mac) {
+// MAC 주소 검증 함수 (빈 값은 미지정으로 취급)
+// AA:BB:CC:DD:EE:FF, AA-BB-CC-DD-EE-FF, aabb.ccdd.eeff, aabbccddeeff 형식 허용 (대소문자 무관)
+pub(super) fn validate_mac_address(mac: &str) -> Result<(), validator::ValidationError> {
+    if mac.trim().is_empty() || parse_mac_address(mac).is_ok() {
         Ok(())
     } else {
         Err(validator::ValidationError::new("invalid_mac_address"))
     }
 }
 
+// IPv4/IPv6 주소 검증 함수 (빈 값은 미지정으로 취급)
+pub(super) fn validate_ip_address(ip: &str) -> Result<(), validator::ValidationError> {
+    if ip.trim().is_empty() || ip.trim().parse::<std::net::IpAddr>().is_ok() {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_ip_address"))
+    }
+}
+
+// 장치 주소 검증 함수 (IPv4, IPv6 또는 호스트 이름, 빈 값은 미지정으로 취급)
+fn validate_host(host: &str) -> Result<(), validator::ValidationError> {
+    if host.trim().is_empty() || Host::parse(host).is_some() {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_host"))
+    }
+}
+
+// SecureOn 비밀번호 검증 함수 (빈 값은 비밀번호 제거로 취급)
+fn validate_secureon_password(password: &str) -> Result<(), validator::ValidationError> {
+    if password.trim().is_empty() || parse_secureon_password(password.trim()).is_ok() {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_secureon_password"))
+    }
+}
+
+// SSH 개인키 검증 함수 (빈 값은 개인키 제거로 취급)
+fn validate_ssh_private_key(key: &str) -> Result<(), validator::ValidationError> {
+    if key.trim().is_empty() || is_valid_private_key(key.trim()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_ssh_private_key"))
+    }
+}
+
+// 전송 방식 검증 함수
+fn validate_transport(transport: &str) -> Result<(), validator::ValidationError> {
+    match WakeTransport::parse(transport) {
+        Some(_) => Ok(()),
+        None => Err(validator::ValidationError::new("invalid_transport")),
+    }
+}
+
+// 전송 대상 목록 검증 함수
+fn validate_wake_targets(targets: &[String]) -> Result<(), validator::ValidationError> {
+    if targets.iter().all(|target| WakeTarget::parse(target).is_some()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_wake_targets"))
+    }
+}
+
+// 넷마스크 검증 함수 (연속된 비트로 구성된 IPv4 마스크만 허용)
+fn validate_netmask(netmask: &str) -> Result<(), validator::ValidationError> {
+    if netmask.trim().is_empty() {
+        return Ok(());
+    }
+    match netmask.trim().parse::<std::net::Ipv4Addr>() {
+        Ok(mask) if u32::from(mask).leading_ones() + u32::from(mask).trailing_zeros() == 32 => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_netmask")),
+    }
+}
+
+// 장치 주소로 쓰기 어려운 MAC 주소 검증 함수
+// 멀티캐스트 비트가 켜진 주소는 항상 거부하고, 로컬 관리 주소는 allow_local_mac이 true일 때만 허용 (가상머신 등)
+pub(super) fn check_mac_bits(mac: &str, allow_local_mac: bool) -> Result<(), validator::ValidationError> {
+    let Ok(info) = mac_info(mac) else {
+        return Ok(());
+    };
+    if info.multicast {
+        return Err(validator::ValidationError::new("multicast_mac")
+            .with_message("멀티캐스트 MAC 주소는 장치 주소로 사용할 수 없습니다 (첫 바이트의 최하위 비트 확인)".into()));
+    }
+    if info.locally_administered && !allow_local_mac {
+        return Err(validator::ValidationError::new("locally_administered_mac")
+            .with_message("로컬 관리 MAC 주소입니다. 가상머신 등 의도한 주소라면 allow_local_mac을 지정하세요".into()));
+    }
+    Ok(())
+}
+
+// 요청한 MAC 주소의 멀티캐스트/로컬 관리 비트 확인
+// 수정시 저장된 주소와 같다면 다시 확인하지 않음 (이미 허용된 로컬 관리 주소를 allow_local_mac 없이 저장할 수 있도록)
+fn validate_mac_bits(req: &PostDeviceReq, stored: Option<&str>) -> Result<(), SystemError> {
+    // 형식 오류는 validate_mac_address에서 처리
+    let Some(mac) = none_if_empty(&req.mac).and_then(|mac| normalize_mac_address(&mac).ok()) else {
+        return Ok(());
+    };
+    if stored == Some(mac.as_str()) {
+        return Ok(());
+    }
+    check_mac_bits(&mac, req.allow_local_mac.unwrap_or(false))
+        .map_err(|e| SystemError::APIError(400, 0, format!("mac: {}", e.message.unwrap_or_default())))
+}
+
+// 헬스 체크 항목 검증 함수 (종류별 필수 값 확인)
+fn validate_probe(probe: &PostProbeReq) -> Result<(), validator::ValidationError> {
+    let valid = match probe.kind.as_str() {
+        "tcp" => probe.port.is_some(),
+        "http" => probe.url.as_deref().is_some_and(|url| {
+            let url = url.trim();
+            url.starts_with("http://") || url.starts_with("https://")
+        }),
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_probe")
+            .with_message("tcp는 포트, http는 http:// 또는 https://로 시작하는 URL이 필요합니다".into()))
+    }
+}
+
+// 전원 제어 드라이버 검증 함수
+fn validate_power_driver(driver: &str) -> Result<(), validator::ValidationError> {
+    match PowerDriverKind::parse(driver) {
+        Some(_) => Ok(()),
+        None => Err(validator::ValidationError::new("invalid_power_driver")),
+    }
+}
+
+// BMC 주소 검증 함수 (빈 값은 미지정으로 취급)
+fn validate_bmc_url(url: &str) -> Result<(), validator::ValidationError> {
+    let url = url.trim();
+    if url.is_empty() || url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_bmc_url"))
+    }
+}
+
+// Redfish 시스템 ID 검증 함수 (BMC 리소스 경로에 사용)
+fn validate_bmc_system_id(id: &str) -> Result<(), validator::ValidationError> {
+    if id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') && !id.contains("..") {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_bmc_system_id"))
+    }
+}
+
+// 동작 이름 검증 함수 (API 경로에 사용되므로 영문/숫자/-/_만 허용)
+fn validate_action_name(name: &str) -> Result<(), validator::ValidationError> {
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_action_name"))
+    }
+}
+
+fn validate_action_method(method: &str) -> Result<(), validator::ValidationError> {
+    if ACTION_METHODS.contains(&method.to_uppercase().as_str()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_action_method"))
+    }
+}
+
+fn validate_action_url(url: &str) -> Result<(), validator::ValidationError> {
+    let url = url.trim();
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_action_url"))
+    }
+}
+
+fn validate_action_headers(headers: &BTreeMap<String, String>) -> Result<(), validator::ValidationError> {
+    if headers.iter().all(|(name, value)| is_valid_header(name, value)) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_action_headers"))
+    }
+}
+
+// 태그 검증 함수 (앞뒤 공백 제외 1~64자)
+fn validate_tags(tags: &[String]) -> Result<(), validator::ValidationError> {
+    if tags.iter().all(|tag| (1..=MAX_TAG_LENGTH).contains(&tag.trim().chars().count())) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_tags"))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct GetActionRes {
+    pub id: i64,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    // 헤더 값은 응답에 포함하지 않음
+    pub header_names: Vec<String>,
+    pub body: Option<String>,
+    pub expected_status: Option<i64>,
+    pub timeout_ms: i64,
+}
+
+impl GetActionRes {
+    fn new(action: &DeviceAction, crypto: &CryptoConfig) -> Self {
+        Self {
+            id: action.id,
+            name: action.name.clone(),
+            method: action.method.clone(),
+            url: action.url.clone(),
+            header_names: decrypt_headers(crypto, action)
+                .map(|headers| headers.into_keys().collect())
+                .unwrap_or_default(),
+            body: action.body.clone(),
+            expected_status: action.expected_status,
+            timeout_ms: action.timeout_ms,
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(crate = "rocket::serde")]
 pub struct GetDeviceRes {
     pub id: i64,
     pub name: String,
     pub mac: String,
+    // MAC 주소로 조회한 제조사
+    pub vendor: Option<String>,
     pub ip: String,   
+    // ip가 호스트 이름인 경우 조회한 주소 (IP로 지정된 경우 동일한 값, 조회 실패시 null)
+    pub resolved_ip: Option<String>,
     pub order_num: i64,
+    pub broadcast: Option<String>,
+    pub netmask: Option<String>,
+    pub port: i64,
+    pub interface: Option<String>,
+    // 비밀번호 자체는 응답에 포함하지 않음
+    pub has_secureon: bool,
+    pub transport: String,
+    pub vlan_id: Option<i64>,
+    pub burst_count: Option<i64>,
+    pub burst_interval_ms: Option<i64>,
+    pub wake_targets: Option<Vec<String>>,
+    pub verify_port: Option<i64>,
+    pub ssh_user: Option<String>,
+    pub ssh_port: Option<i64>,
+    pub has_ssh_password: bool,
+    pub has_ssh_private_key: bool,
+    pub ssh_command: Option<String>,
+    pub ssh_host_key: Option<String>,
+    pub sleep_on_lan: bool,
+    pub power_driver: String,
+    // 드라이버 설정 (비밀번호는 응답에 포함하지 않음)
+    pub power_config: Option<serde_json::Value>,
+    pub has_power_password: bool,
+    pub bmc_url: Option<String>,
+    pub bmc_user: Option<String>,
+    pub has_bmc_password: bool,
+    pub bmc_system_id: Option<String>,
+    pub bmc_insecure: bool,
+    pub group_id: Option<i64>,
+    pub tags: Vec<String>,
+    // 이 장치보다 먼저 켜야 하는 장치
+    pub depends_on: Vec<i64>,
+    // 상태 모니터링 결과 (unknown, online, offline)
+    pub status: String,
+    pub last_seen: Option<String>,
+    // 서비스 헬스 체크 설정 및 마지막 결과
+    pub probes: Vec<Probe>,
+    // 사용자 정의 HTTP 동작
+    pub actions: Vec<GetActionRes>,
 }
 
-#[get("/devices")]
-pub async fn get_devices(db: &Db, _auth: AuthUser) -> Result<Json<Vec<GetDeviceRes>>, SystemError> {
-    let devices = Device::get_all(db).await?;
-    let mut device_list = Vec::new();
-    for device in devices {
-        device_list.push(GetDeviceRes {
+impl From<Device> for GetDeviceRes {
+    fn from(device: Device) -> Self {
+        let (power_config, has_power_password) = public_config(device.power_config.as_deref());
+        Self {
             id: device.id,
             name: device.name,
+            vendor: lookup_vendor(&device.mac),
             mac: device.mac,
-            ip: device.ip,       
+            resolved_ip: None,
+            ip: device.ip,
             order_num: device.order_num,
-        });
+            broadcast: device.broadcast,
+            netmask: device.netmask,
+            port: device.port,
+            interface: device.interface,
+            has_secureon: device.secureon.is_some(),
+            transport: device.transport,
+            vlan_id: device.vlan_id,
+            burst_count: device.burst_count,
+            burst_interval_ms: device.burst_interval_ms,
+            wake_targets: device.wake_targets
+                .map(|targets| targets.split(',').map(str::to_string).collect()),
+            verify_port: device.verify_port,
+            ssh_user: device.ssh_user,
+            ssh_port: device.ssh_port,
+            has_ssh_password: device.ssh_password.is_some(),
+            has_ssh_private_key: device.ssh_private_key.is_some(),
+            ssh_command: device.ssh_command,
+            ssh_host_key: device.ssh_host_key,
+            sleep_on_lan: device.sleep_on_lan,
+            power_driver: device.power_driver,
+            power_config,
+            has_power_password,
+            bmc_url: device.bmc_url,
+            bmc_user: device.bmc_user,
+            has_bmc_password: device.bmc_password.is_some(),
+            bmc_system_id: device.bmc_system_id,
+            bmc_insecure: device.bmc_insecure,
+            group_id: device.group_id,
+            tags: Vec::new(),
+            depends_on: Vec::new(),
+            status: device.status,
+            last_seen: device.last_seen,
+            probes: Vec::new(),
+            actions: Vec::new(),
+        }
+    }
+}
+
+impl GetDeviceRes {
+    fn with_probes(mut self, probes: &[Probe]) -> Self {
+        self.probes = probes.iter().filter(|probe| probe.device_id == self.id).cloned().collect();
+        self
+    }
+
+    fn with_actions(mut self, actions: &[DeviceAction], crypto: &CryptoConfig) -> Self {
+        self.actions = actions.iter()
+            .filter(|action| action.device_id == self.id)
+            .map(|action| GetActionRes::new(action, crypto))
+            .collect();
+        self
+    }
+
+    fn with_tags(mut self, tags: &[DeviceTag]) -> Self {
+        self.tags = tags.iter()
+            .filter(|tag| tag.device_id == self.id)
+            .map(|tag| tag.tag.clone())
+            .collect();
+        self
+    }
+
+    fn with_dependencies(mut self, dependencies: &[DeviceDependency]) -> Self {
+        self.depends_on = dependencies.iter()
+            .filter(|dependency| dependency.device_id == self.id)
+            .map(|dependency| dependency.depends_on)
+            .collect();
+        self
+    }
+
+    // IP 주소는 조회 없이 그대로, 호스트 이름은 캐시된 조회 결과 사용 (목록 조회마다 DNS 조회 방지)
+    async fn with_resolved_ip(mut self) -> Self {
+        self.resolved_ip = resolve_host_cached(&self.ip).await.map(|ip| ip.to_string());
+        self
     }
+}
+
+// group, tag 지정시 해당 그룹에 속하거나 해당 태그가 붙은 장치만 조회 (둘 다 지정하면 모두 만족하는 장치)
+#[get("/devices?<group>&<tag>")]
+pub async fn get_devices(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, group: Option<i64>, tag: Option<&str>)
+                         -> Result<Json<Vec<GetDeviceRes>>, SystemError> {
+    let devices = Device::get_all(db).await?;
+    let probes = Probe::get_all(db).await?;
+    let actions = DeviceAction::get_all(db).await?;
+    let tags = DeviceTag::get_all(db).await?;
+    let dependencies = DeviceDependency::get_all(db).await?;
+    let tag = tag.map(str::trim);
+    let device_list = join_all(devices.into_iter()
+        .filter(|device| group.is_none() || device.group_id == group)
+        .filter(|device| tag.is_none_or(|tag| tags.iter().any(|t| t.device_id == device.id && t.tag == tag)))
+        .map(|device| GetDeviceRes::from(device).with_probes(&probes).with_actions(&actions, crypto).with_tags(&tags)
+            .with_dependencies(&dependencies).with_resolved_ip()))
+        .await;
     Ok(Json(device_list))
 }
 
 #[get("/device/<id>")]
-pub async fn get_device(db: &Db, _auth: AuthUser, id: i64) -> Result<Json<GetDeviceRes>, SystemError> {
+pub async fn get_device(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, id: i64) -> Result<Json<GetDeviceRes>, SystemError> {
     let device = Device::get(db, id).await?
         .ok_or(PredefinedApiError::NotFound.get())?;
+    let probes = Probe::get_by_device(db, id).await?;
+    let actions = DeviceAction::get_by_device(db, id).await?;
+    let tags = DeviceTag::get_by_device(db, id).await?;
+    let dependencies = DeviceDependency::get_by_device(db, id).await?;
 
-    let device_res = GetDeviceRes {
-        id: device.id,
-        name: device.name,
-        mac: device.mac,
-        ip: device.ip,       
-        order_num: device.order_num,
-    };
-    
-    Ok(Json(device_res))
+    Ok(Json(GetDeviceRes::from(device).with_probes(&probes).with_actions(&actions, crypto).with_tags(&tags)
+        .with_dependencies(&dependencies).with_resolved_ip().await))
 }
 
 #[derive(Deserialize, Validate)]
 #[serde(crate = "rocket::serde")]
+// 선택 항목은 미지정시 기존 값 유지, null이나 빈 값이면 제거 (생성시에는 기본값 사용)
 pub struct PostDeviceReq {
     pub id: Option<i64>,
     #[validate(length(min = 1, message = "이름은 공백일 수 없습니다"))]
     pub name: String,
-    #[validate(custom(function = "validate_mac_address", message = "올바른 MAC 주소 형식이 아닙니다 (예: AA:BB:CC:DD:EE:FF)"))]
-    pub mac: String,
+    #[validate(custom(function = "validate_mac_address", message = "올바른 MAC 주소 형식이 아닙니다 (예: AA:BB:CC:DD:EE:FF, aabb.ccdd.eeff)"))]
+    // 생성시 미지정이면 ip로 이웃(ARP) 테이블에서 조회, 수정시 미지정이면 기존 값 유지
+    pub mac: Option<String>,
+    // 로컬 관리(U/L 비트) MAC 주소 허용 여부
+    pub allow_local_mac: Option<bool>,
+    // IPv4, IPv6 또는 호스트 이름 (호스트 이름은 웨이크/상태 확인 시점에 조회)
+    #[validate(custom(function = "validate_host", message = "올바른 IP 주소 또는 호스트 이름이 아닙니다 (예: 192.168.0.10, fe80::1, my-pc.local)"))]
     pub ip: String, 
+    // IPv4 브로드캐스트 주소 또는 IPv6 멀티캐스트/유니캐스트 주소
+    #[validate(custom(function = "validate_ip_address", message = "올바른 브로드캐스트 주소가 아닙니다 (예: 192.168.0.255, ff02::1)"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub broadcast: Option<Option<String>>,
+    #[validate(custom(function = "validate_netmask", message = "올바른 넷마스크가 아닙니다 (예: 255.255.255.0)"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub netmask: Option<Option<String>>,
+    // null이면 9번 포트
+    #[validate(range(min = 1, message = "포트는 1~65535 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub port: Option<Option<u16>>,
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub interface: Option<Option<String>>,
+    #[validate(custom(function = "validate_secureon_password", message = "올바른 SecureOn 비밀번호 형식이 아닙니다 (예: 00:11:22:33:44:55 또는 192.168.1.1)"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub secureon: Option<Option<String>>,
+    // null이면 udp
+    #[validate(custom(function = "validate_transport", message = "전송 방식은 udp, ethernet, both 중 하나여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub transport: Option<Option<String>>,
+    #[validate(range(min = 1, max = 4094, message = "VLAN ID는 1~4094 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub vlan_id: Option<Option<i64>>,
+    // 전송 정책 (값이 없으면 Rocket.toml의 [wol] 기본값 사용)
+    #[validate(range(min = 1, max = MAX_BURST_COUNT, message = "전송 횟수는 1~20 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub burst_count: Option<Option<u32>>,
+    #[validate(range(max = MAX_BURST_INTERVAL_MS, message = "전송 간격은 0~10000ms 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub burst_interval_ms: Option<Option<u64>>,
+    #[validate(custom(function = "validate_wake_targets", message = "전송 대상은 broadcast, subnet, unicast 중에서 선택해야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub wake_targets: Option<Option<Vec<String>>>,
+    #[validate(range(min = 1, message = "포트는 1~65535 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub verify_port: Option<Option<u16>>,
+    // SSH 원격 종료 설정
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub ssh_user: Option<Option<String>>,
+    #[validate(range(min = 1, message = "포트는 1~65535 사이여야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub ssh_port: Option<Option<u16>>,
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub ssh_password: Option<Option<String>>,
+    #[validate(custom(function = "validate_ssh_private_key", message = "올바른 SSH 개인키가 아닙니다 (암호가 없는 OpenSSH/PEM 형식만 지원)"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub ssh_private_key: Option<Option<String>>,
+    // 값이 없으면 Rocket.toml의 [ssh] 기본 명령 사용
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub ssh_command: Option<Option<String>>,
+    // 저장된 호스트 키 지문을 지우고 다음 접속시 다시 신뢰 (장치 재설치 등)
+    pub reset_ssh_host_key: Option<bool>,
+    // Sleep-on-LAN 에이전트 실행 여부 (null이면 false)
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub sleep_on_lan: Option<Option<bool>>,
+    // 전원 제어 드라이버 (미지정시 기존 값 유지, 생성시 wol)
+    #[validate(custom(function = "validate_power_driver", message = "전원 드라이버는 wol, redfish, ipmi, smart_plug 중 하나여야 합니다"))]
+    pub power_driver: Option<String>,
+    // 드라이버별 설정 (미지정시 기존 값 유지, null이면 제거, password는 미지정시 기존 값 유지)
+    pub power_config: Option<serde_json::Value>,
+    // Redfish BMC 접속 정보
+    #[validate(custom(function = "validate_bmc_url", message = "BMC 주소는 http:// 또는 https://로 시작해야 합니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub bmc_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub bmc_user: Option<Option<String>>,
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub bmc_password: Option<Option<String>>,
+    #[validate(custom(function = "validate_bmc_system_id", message = "시스템 ID는 영문, 숫자, -, _, .만 사용할 수 있습니다"))]
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub bmc_system_id: Option<Option<String>>,
+    // null이면 false
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub bmc_insecure: Option<Option<bool>>,
+    // 소속 그룹 (미지정시 기존 값 유지, null이면 그룹에서 제외)
+    #[serde(default, deserialize_with = "crate::api::deserialize_nullable")]
+    pub group_id: Option<Option<i64>>,
+    // 미지정시 기존 태그 유지, 지정시 목록 전체를 교체
+    #[validate(custom(function = "validate_tags", message = "태그는 1~64자여야 합니다"))]
+    pub tags: Option<Vec<String>>,
+    // 웨이크 전에 먼저 켜고 응답을 확인할 장치 (미지정시 기존 값 유지, 지정시 목록 전체를 교체)
+    pub depends_on: Option<Vec<i64>>,
+    // 미지정시 기존 헬스 체크 유지, 지정시 목록 전체를 교체
+    #[validate(nested)]
+    pub probes: Option<Vec<PostProbeReq>>,
+    // 미지정시 기존 동작 유지, 지정시 목록 전체를 교체
+    #[validate(nested)]
+    pub actions: Option<Vec<PostActionReq>>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct PostActionReq {
+    // 기존 항목 수정시 지정
+    pub id: Option<i64>,
+    #[validate(length(min = 1, max = 64, message = "이름은 1~64자여야 합니다"))]
+    #[validate(custom(function = "validate_action_name", message = "이름은 영문, 숫자, -, _만 사용할 수 있습니다"))]
+    pub name: String,
+    #[validate(custom(function = "validate_action_method", message = "메서드는 GET, POST, PUT, PATCH, DELETE 중 하나여야 합니다"))]
+    pub method: String,
+    #[validate(custom(function = "validate_action_url", message = "URL은 http:// 또는 https://로 시작해야 합니다"))]
+    pub url: String,
+    // 기존 항목 수정시 미지정이면 기존 헤더 유지 (암호화되어 저장되며 응답에는 이름만 포함)
+    #[validate(custom(function = "validate_action_headers", message = "올바르지 않은 헤더 이름 또는 값이 있습니다"))]
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body: Option<String>,
+    #[validate(range(min = 100, max = 599, message = "상태 코드는 100~599 사이여야 합니다"))]
+    pub expected_status: Option<u16>,
+    #[validate(range(min = 100, max = 60000, message = "타임아웃은 100~60000ms 사이여야 합니다"))]
+    pub timeout_ms: Option<u32>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+#[validate(schema(function = "validate_probe"))]
+pub struct PostProbeReq {
+    // 기존 항목 수정시 지정 (마지막 결과가 유지됨)
+    pub id: Option<i64>,
+    #[validate(length(min = 1, message = "이름은 공백일 수 없습니다"))]
+    pub name: String,
+    // tcp 또는 http
+    pub kind: String,
+    #[validate(range(min = 1, message = "포트는 1~65535 사이여야 합니다"))]
+    pub port: Option<u16>,
+    pub url: Option<String>,
+    #[validate(range(min = 100, max = 599, message = "상태 코드는 100~599 사이여야 합니다"))]
+    pub expected_status: Option<u16>,
+    pub body_contains: Option<String>,
+    #[validate(range(min = MIN_PROBE_INTERVAL_SECS, message = "확인 주기는 5초 이상이어야 합니다"))]
+    pub interval_secs: Option<u32>,
+    #[validate(range(min = 100, max = 60000, message = "타임아웃은 100~60000ms 사이여야 합니다"))]
+    pub timeout_ms: Option<u32>,
+}
+
+impl From<&PostProbeReq> for Probe {
+    fn from(req: &PostProbeReq) -> Self {
+        let is_http = req.kind == "http";
+        Self {
+            id: req.id.unwrap_or_default(),
+            name: req.name.clone(),
+            kind: req.kind.clone(),
+            port: if is_http { None } else { req.port.map(i64::from) },
+            url: if is_http { none_if_empty(&req.url) } else { None },
+            expected_status: if is_http { req.expected_status.map(i64::from) } else { None },
+            body_contains: if is_http { none_if_empty(&req.body_contains) } else { None },
+            interval_secs: req.interval_secs.unwrap_or(DEFAULT_PROBE_INTERVAL_SECS) as i64,
+            timeout_ms: req.timeout_ms.unwrap_or(DEFAULT_PROBE_TIMEOUT_MS) as i64,
+            ..Default::default()
+        }
+    }
+}
+
+// 요청의 헬스 체크 목록을 저장 (목록이 지정된 경우에만)
+async fn save_probes(conn: &mut SqliteConnection, device_id: i64, req: &PostDeviceReq) -> Result<(), SystemError> {
+    if let Some(probes) = &req.probes {
+        let probes: Vec<Probe> = probes.iter().map(Probe::from).collect();
+        Probe::save_for_device(conn, device_id, &probes).await?;
+    }
+    Ok(())
+}
+
+// 요청의 HTTP 동작 목록을 저장 (목록이 지정된 경우에만, 헤더 미지정시 existing의 헤더 유지)
+async fn save_actions(conn: &mut SqliteConnection, device_id: i64, req: &PostDeviceReq, existing: &[DeviceAction],
+                      crypto: &CryptoConfig) -> Result<(), SystemError> {
+    let Some(reqs) = &req.actions else {
+        return Ok(());
+    };
+    let mut actions = Vec::with_capacity(reqs.len());
+    for action in reqs {
+        let headers = match &action.headers {
+            Some(headers) if headers.is_empty() => None,
+            Some(headers) => Some(encrypt(crypto, &json!(headers).to_string())?),
+            None => existing.iter()
+                .find(|old| Some(old.id) == action.id)
+                .and_then(|old| old.headers.clone()),
+        };
+        actions.push(DeviceAction {
+            id: action.id.unwrap_or_default(),
+            device_id,
+            name: action.name.clone(),
+            method: action.method.to_uppercase(),
+            url: action.url.trim().to_string(),
+            headers,
+            body: none_if_empty(&action.body),
+            expected_status: action.expected_status.map(i64::from),
+            timeout_ms: action.timeout_ms.unwrap_or(DEFAULT_ACTION_TIMEOUT_MS) as i64,
+        });
+    }
+    DeviceAction::save_for_device(conn, device_id, &actions).await.map_err(map_duplicated)?;
+    Ok(())
+}
+
+// 요청의 태그 목록을 저장 (목록이 지정된 경우에만)
+async fn save_tags(conn: &mut SqliteConnection, device_id: i64, req: &PostDeviceReq) -> Result<(), SystemError> {
+    if let Some(tags) = &req.tags {
+        let mut tags: Vec<String> = tags.iter().map(|tag| tag.trim().to_string()).collect();
+        tags.sort();
+        tags.dedup();
+        DeviceTag::set_for_device(conn, device_id, &tags).await?;
+    }
+    Ok(())
+}
+
+// 지정된 그룹이 존재하는지 확인
+async fn check_group(db: &Db, req: &PostDeviceReq) -> Result<(), SystemError> {
+    if let Some(Some(group_id)) = req.group_id
+        && DeviceGroup::get(db, group_id).await?.is_none() {
+        return Err(SystemError::APIError(400, 0, format!("Group not found: {}", group_id)));
+    }
+    Ok(())
+}
+
+// 의존 장치가 존재하고 순환이 생기지 않는지 확인 (생성시에는 device_id 없음)
+async fn check_dependencies(db: &Db, device_id: Option<i64>, req: &PostDeviceReq) -> Result<(), SystemError> {
+    let Some(depends_on) = &req.depends_on else {
+        return Ok(());
+    };
+    for &dependency in depends_on {
+        if Some(dependency) == device_id {
+            return Err(SystemError::APIError(400, 0, "A device cannot depend on itself".to_string()));
+        }
+        if Device::get(db, dependency).await?.is_none() {
+            return Err(SystemError::APIError(400, 0, format!("Dependency device not found: {}", dependency)));
+        }
+    }
+    // 새 장치에 의존하는 장치는 아직 없으므로 순환은 수정시에만 생길 수 있음
+    if let Some(device_id) = device_id {
+        let dependencies = DeviceDependency::get_all(db).await?;
+        if let Some(cycle) = find_cycle(&dependencies, device_id, depends_on) {
+            let cycle: Vec<String> = cycle.iter().map(i64::to_string).collect();
+            return Err(SystemError::APIError(400, 0, format!("Dependency cycle detected: {}", cycle.join(" -> "))));
+        }
+    }
+    Ok(())
+}
+
+// 요청의 의존 장치 목록을 저장 (목록이 지정된 경우에만)
+async fn save_dependencies(conn: &mut SqliteConnection, device_id: i64, req: &PostDeviceReq) -> Result<(), SystemError> {
+    if let Some(depends_on) = &req.depends_on {
+        DeviceDependency::set_for_device(conn, device_id, depends_on).await?;
+    }
+    Ok(())
+}
+
+// 요청 값을 장치 모델에 반영
+// 지정된 선택 입력값만 반영 (null이나 빈 문자열이면 제거)
+fn update_nullable(target: &mut Option<String>, value: &Option<Option<String>>) {
+    if let Some(value) = value {
+        *target = none_if_empty(value);
+    }
+}
+
+// 지정된 비밀 값만 암호화하여 반영 (null이나 빈 문자열이면 제거)
+fn update_secret(crypto: &CryptoConfig, target: &mut Option<String>, value: &Option<Option<String>>) -> Result<(), SystemError> {
+    if let Some(value) = value {
+        *target = match none_if_empty(value) {
+            Some(secret) => Some(encrypt(crypto, &secret)?),
+            None => None,
+        };
+    }
+    Ok(())
+}
+
+fn apply_device_request(device: &mut Device, req: &PostDeviceReq, crypto: &CryptoConfig) -> Result<(), SystemError> {
+    device.name = req.name.clone();
+    if let Some(mac) = none_if_empty(&req.mac) {
+        device.mac = normalize_mac_address(&mac)?;
+    }
+    device.ip = req.ip.trim().to_string();
+    update_nullable(&mut device.broadcast, &req.broadcast);
+    update_nullable(&mut device.netmask, &req.netmask);
+    if let Some(port) = req.port {
+        device.port = port.unwrap_or(DEFAULT_WOL_PORT) as i64;
+    }
+    update_nullable(&mut device.interface, &req.interface);
+    if let Some(transport) = &req.transport {
+        let transport = transport.as_deref().and_then(WakeTransport::parse).unwrap_or(WakeTransport::Udp);
+        device.transport = transport.as_str().to_string();
+    }
+    // 기존 값과 합친 결과로 확인 (인터페이스만 지우는 경우 등)
+    if WakeTransport::parse(&device.transport).is_some_and(WakeTransport::uses_ethernet) && device.interface.is_none() {
+        return Err(SystemError::APIError(400, 0, "Raw ethernet transport requires a network interface name".to_string()));
+    }
+    if let Some(vlan_id) = req.vlan_id {
+        device.vlan_id = vlan_id;
+    }
+    if let Some(burst_count) = req.burst_count {
+        device.burst_count = burst_count.map(i64::from);
+    }
+    if let Some(burst_interval_ms) = req.burst_interval_ms {
+        device.burst_interval_ms = burst_interval_ms.map(|i| i as i64);
+    }
+    if let Some(wake_targets) = &req.wake_targets {
+        device.wake_targets = wake_targets.as_ref()
+            .map(|targets| targets.iter()
+                .filter_map(|target| WakeTarget::parse(target))
+                .map(WakeTarget::as_str)
+                .collect::<Vec<_>>()
+                .join(","))
+            .filter(|targets| !targets.is_empty());
+    }
+    if let Some(verify_port) = req.verify_port {
+        device.verify_port = verify_port.map(i64::from);
+    }
+    update_secret(crypto, &mut device.secureon, &req.secureon)?;
+    update_nullable(&mut device.ssh_user, &req.ssh_user);
+    if let Some(ssh_port) = req.ssh_port {
+        device.ssh_port = ssh_port.map(i64::from);
+    }
+    update_nullable(&mut device.ssh_command, &req.ssh_command);
+    update_secret(crypto, &mut device.ssh_password, &req.ssh_password)?;
+    update_secret(crypto, &mut device.ssh_private_key, &req.ssh_private_key)?;
+    if req.reset_ssh_host_key == Some(true) {
+        device.ssh_host_key = None;
+    }
+    if let Some(sleep_on_lan) = req.sleep_on_lan {
+        device.sleep_on_lan = sleep_on_lan.unwrap_or(false);
+    }
+    if let Some(power_driver) = req.power_driver.as_deref().and_then(PowerDriverKind::parse) {
+        device.power_driver = power_driver.as_str().to_string();
+    } else if device.power_driver.is_empty() {
+        device.power_driver = PowerDriverKind::Wol.as_str().to_string();
+    }
+    if let Some(config) = &req.power_config {
+        device.power_config = seal_config(crypto, config, device.power_config.as_deref())?;
+    }
+    update_nullable(&mut device.bmc_url, &req.bmc_url);
+    update_nullable(&mut device.bmc_user, &req.bmc_user);
+    update_secret(crypto, &mut device.bmc_password, &req.bmc_password)?;
+    update_nullable(&mut device.bmc_system_id, &req.bmc_system_id);
+    if let Some(bmc_insecure) = req.bmc_insecure {
+        device.bmc_insecure = bmc_insecure.unwrap_or(false);
+    }
+    if let Some(group_id) = req.group_id {
+        device.group_id = group_id;
+    }
+    // 설정이 드라이버에 맞는지 확인
+    driver_for(crypto, device)?;
+    Ok(())
 }
 
 #[post("/device", data = "<req>")]
-pub async fn create_device(db: &Db, _auth: AuthUser, req: Json<PostDeviceReq>,
-) -> Result<Status, SystemError> {
+pub async fn create_device(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, wol_config: &State<WolConfig>,
+                           events: &State<EventBus>, req: Json<PostDeviceReq>) -> Result<Status, SystemError> {
     validate_request(&*req)?;
+    validate_mac_bits(&req, None)?;
+    check_group(db, &req).await?;
+    check_dependencies(db, None, &req).await?;
 
     let max_order = Device::get_max_order_num(&db.0).await?;
 
-    let device = Device{
-        id: 0,
-        name: req.name.clone(),
-        mac: req.mac.clone(),
-        ip: req.ip.to_string(),       
+    let mut device = Device{
         order_num: max_order + 1,
+        port: DEFAULT_WOL_PORT as i64,
+        transport: WakeTransport::Udp.as_str().to_string(),
+        ..Default::default()
     };
-    Device::insert(db,&device).await?;
+    apply_device_request(&mut device, &req, crypto)?;
+    if device.mac.is_empty() {
+        device.mac = resolve_device_mac(&device.ip, wol_config).await?;
+        // 이웃 테이블에서 찾은 주소도 직접 입력한 주소와 같은 기준으로 확인
+        check_mac_bits(&device.mac, req.allow_local_mac.unwrap_or(false))
+            .map_err(|e| SystemError::APIError(400, 0, format!("mac: {} ({})", e.message.unwrap_or_default(), device.mac)))?;
+    }
+    // 장치와 헬스 체크, 동작, 태그, 의존 관계를 함께 저장 (하나라도 실패하면 모두 취소)
+    let mut tx = db.begin().await?;
+    let device = Device::insert(&mut tx, &device).await.map_err(map_duplicated)?;
+    save_probes(&mut tx, device.id, &req).await?;
+    save_actions(&mut tx, device.id, &req, &[], crypto).await?;
+    save_tags(&mut tx, device.id, &req).await?;
+    save_dependencies(&mut tx, device.id, &req).await?;
+    tx.commit().await?;
+    events.publish(DeviceEvent::DeviceCreated { device_id: device.id });
     Ok(Status::Ok)
 }
 
 
+// IP로 이웃 테이블을 조회하여 MAC 주소를 구함
+async fn resolve_device_mac(host: &str, wol_config: &WolConfig) -> Result<String, SystemError> {
+    if host.trim().is_empty() {
+        return Err(SystemError::APIError(400, 0, "MAC address is required when no IP address is given".to_string()));
+    }
+    let ip = resolve_host(host).await
+        .ok_or(SystemError::APIError(400, 0, format!("Could not resolve host: {}", host.trim())))?;
+    let entry = resolve_mac(ip, true, Duration::from_millis(wol_config.probe_timeout_ms)).await?
+        .ok_or(SystemError::APIError(400, 0, format!("Could not resolve MAC address for {}", ip)))?;
+    Ok(entry.mac)
+}
+
 #[put("/device", data = "<req>")]
-pub async fn update_device(db: &Db, _auth: AuthUser, req: Json<PostDeviceReq>)
-                           -> Result<Status, SystemError> {
+pub async fn update_device(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, events: &State<EventBus>,
+                           req: Json<PostDeviceReq>) -> Result<Status, SystemError> {
     validate_request(&*req)?;
     
     if req.id.is_none() {
         return Err(PredefinedApiError::InvalidRequest.get());
     }
+    check_group(db, &req).await?;
+    check_dependencies(db, req.id, &req).await?;
     
     let mut device = Device::get(db, req.id.unwrap()).await?
         .ok_or(PredefinedApiError::NotFound.get())?;
+    validate_mac_bits(&req, Some(&device.mac))?;
 
     // 필드 업데이트
-    device.name = req.name.clone();
-    device.mac = req.mac.clone();
-    device.ip = req.ip.clone();  
+    apply_device_request(&mut device, &req, crypto)?;
 
-    // DB 업데이트
-    device.update(db).await?;
+    // DB 업데이트 (장치와 관련 항목을 하나의 트랜잭션으로 저장)
+    let actions = DeviceAction::get_by_device(db, device.id).await?;
+    let mut tx = db.begin().await?;
+    device.update(&mut tx).await.map_err(map_duplicated)?;
+    save_probes(&mut tx, device.id, &req).await?;
+    save_actions(&mut tx, device.id, &req, &actions, crypto).await?;
+    save_tags(&mut tx, device.id, &req).await?;
+    save_dependencies(&mut tx, device.id, &req).await?;
+    tx.commit().await?;
+    events.publish(DeviceEvent::DeviceUpdated { device_id: device.id });
 
     Ok(Status::Ok)
 }
 
 #[delete("/device/<id>")]
-pub async fn delete_device(db: &Db, _auth: AuthUser, id: i64) -> Result<Status, SystemError> {
-    Device::delete(&db.0, id).await?;
+pub async fn delete_device(db: &Db, _auth: AuthUser, events: &State<EventBus>, id: i64) -> Result<Status, SystemError> {
+    // 장치와 관련 항목(헬스 체크, 동작과 실행 기록, 태그, 의존 관계)을 하나의 트랜잭션으로 삭제
+    // 씬 단계에서 사용 중인 장치는 씬의 흐름이 바뀌지 않도록 삭제하지 않음 (씬에서 먼저 제거해야 함)
+    let mut tx = db.begin().await?;
+    let scenes = SceneStep::get_scene_names_by_device(&mut tx, id).await?;
+    if !scenes.is_empty() {
+        return Err(SystemError::APIError(409, 0, format!("Device is used by scenes: {}", scenes.join(", "))));
+    }
+    Probe::delete_by_device(&mut tx, id).await?;
+    DeviceAction::delete_by_device(&mut tx, id).await?;
+    DeviceTag::delete_by_device(&mut tx, id).await?;
+    DeviceDependency::delete_by_device(&mut tx, id).await?;
+    Device::delete(&mut tx, id).await?;
+    tx.commit().await?;
+    events.publish(DeviceEvent::DeviceDeleted { device_id: id });
     Ok(Status::Ok)
 }
 
@@ -137,24 +907,209 @@ pub struct MoveDeviceReq {
 }
 
 #[put("/device/move", data = "<req>")]
-pub async fn move_device(db: &Db, _auth: AuthUser,req: Json<MoveDeviceReq>,
+pub async fn move_device(db: &Db, _auth: AuthUser, events: &State<EventBus>, req: Json<MoveDeviceReq>,
 ) -> Result<Status, SystemError> {
-    let direction = match req.direction.to_lowercase().as_str() {
-        "up" => MoveDirection::Up,
-        "down" => MoveDirection::Down,
-        _ => return Err(SystemError::APIError(400, 0, "Invalid direction. Use 'up' or 'down'".to_string())),
-    };
+    let direction = MoveDirection::parse(&req.direction)
+        .ok_or(SystemError::APIError(400, 0, "Invalid direction. Use 'up' or 'down'".to_string()))?;
 
-    Device::move_order(db, req.id, direction).await?;
+    if Device::move_order(db, req.id, direction).await? {
+        events.publish(DeviceEvent::DeviceMoved { device_id: req.id });
+    }
     Ok(Status::Ok)
 }
 
+// 장치의 전원 드라이버로 장치를 켬
+// 의존 장치가 있다면 의존 장치를 먼저 켜서 응답을 확인하는 웨이크 작업을 시작하고 바로 응답 (응답의 job으로 진행 상황 확인)
 #[post("/device/wake/<id>")]
-pub async fn wake_device(db: &Db, _auth: AuthUser, id: i64)
-                         -> Result<Status, SystemError> {
+pub async fn wake_device(db: &Db, _auth: AuthUser, power: &State<PowerResources>, jobs: &State<Arc<WakeJobs>>, id: i64)
+                         -> Result<Json<WakeStart>, SystemError> {
     let device = Device::get(db, id).await?
         .ok_or(PredefinedApiError::NotFound.get())?;
-    
-    send_magic_packet(&device.mac)?;
-    Ok(Status::Ok)
+
+    Ok(Json(start_wake(Arc::clone(jobs), power.inner().clone(), device).await?))
+}
+
+// Sleep-on-LAN 에이전트가 설치된 장치에 MAC을 뒤집은 매직패킷을 전송하여 절전
+#[post("/device/sleep/<id>")]
+pub async fn sleep_device(db: &Db, _auth: AuthUser, wol_config: &State<WolConfig>, transport: &State<SharedTransport>,
+                          events: &State<EventBus>, id: i64) -> Result<Json<WakeReport>, SystemError> {
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+
+    let report = sleep(transport.as_ref(), wol_config, &device).await?;
+    events.publish(DeviceEvent::Sleep { report: report.clone() });
+    Ok(Json(report))
+}
+
+// 장치에 정의된 HTTP 동작을 서버에서 실행하고 결과를 기록
+// (/device/wake/<id>/verify와 경로 형태가 겹치므로 rank 지정)
+#[post("/device/<id>/action/<name>", rank = 2)]
+pub async fn run_device_action(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, events: &State<EventBus>,
+                               id: i64, name: &str) -> Result<Json<ActionResult>, SystemError> {
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let action = DeviceAction::get_by_name(db, id, name).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+
+    let result = run_and_log_action(db, crypto, &device, &action).await?;
+    events.publish(DeviceEvent::ActionRun { device_id: id, action: action.name, success: result.success });
+    Ok(Json(result))
+}
+
+// 장치의 HTTP 동작 실행 기록 (최근 순)
+#[get("/device/<id>/action-log?<limit>", rank = 2)]
+pub async fn get_action_log(db: &Db, _auth: AuthUser, id: i64, limit: Option<i64>) -> Result<Json<Vec<ActionLog>>, SystemError> {
+    let limit = limit.unwrap_or(DEFAULT_ACTION_LOG_LIMIT).clamp(1, 1000);
+    Ok(Json(ActionLog::get_by_device(db, id, limit).await?))
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct PowerStatusRes {
+    pub device_id: i64,
+    pub driver: String,
+    pub state: PowerState,
+}
+
+// 장치의 전원 드라이버로 전원 상태 조회
+#[get("/device/power/<id>")]
+pub async fn get_power_status(db: &Db, _auth: AuthUser, power: &State<PowerResources>, id: i64) -> Result<Json<PowerStatusRes>, SystemError> {
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let ctx = power.context();
+
+    let state = driver_for(ctx.crypto, &device)?.status(&ctx, &device).await?;
+    Ok(Json(PowerStatusRes { device_id: id, driver: device.power_driver, state }))
+}
+
+// 장치의 전원 드라이버로 전원 켜기/끄기 (operation: on, off)
+#[post("/device/power/<id>/<operation>")]
+pub async fn control_power(db: &Db, _auth: AuthUser, power: &State<PowerResources>, events: &State<EventBus>,
+                           id: i64, operation: &str) -> Result<Json<PowerReport>, SystemError> {
+    let operation = PowerOperation::parse(operation)
+        .ok_or(SystemError::APIError(400, 0, "Invalid operation. Use 'on' or 'off'".to_string()))?;
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let ctx = power.context();
+
+    let driver = driver_for(ctx.crypto, &device)?;
+    let report = match operation {
+        PowerOperation::On => driver.power_on(&ctx, &device).await?,
+        PowerOperation::Off => driver.power_off(&ctx, &device).await?,
+    };
+    events.publish(DeviceEvent::Power { device_id: id, operation: operation.as_str().to_string(), state: report.state });
+    Ok(Json(report))
+}
+
+// 장치에 설정된 SSH 계정으로 종료(또는 절전) 명령 실행
+// 호스트 키가 바뀐 경우 reset_ssh_host_key로 초기화해야 함
+#[post("/device/shutdown/<id>")]
+pub async fn shutdown_device(db: &Db, _auth: AuthUser, crypto: &State<CryptoConfig>, ssh_config: &State<SshConfig>,
+                             events: &State<EventBus>, id: i64) -> Result<Json<SshCommandResult>, SystemError> {
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+
+    let result = power_off(db, crypto, ssh_config, &device).await?;
+    events.publish(DeviceEvent::Shutdown { device_id: id, exit_code: result.exit_code });
+    Ok(Json(result))
+}
+
+// 웨이크 후 장치가 실제로 응답할 때까지 확인하는 작업 시작 (진행 상황은 /wake-job/<job_id>로 조회)
+#[post("/device/wake/<id>/verify")]
+pub async fn wake_and_verify_device(db: &Db, _auth: AuthUser, power: &State<PowerResources>, jobs: &State<Arc<WakeJobs>>, id: i64)
+                                    -> Result<Accepted<Json<WakeJob>>, SystemError> {
+    let device = Device::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+
+    let job = start_wake_job(Arc::clone(jobs), power.inner().clone(), device);
+    Ok(Accepted(Json(job)))
+}
+
+#[get("/wake-job/<job_id>")]
+pub async fn get_wake_job(_auth: AuthUser, jobs: &State<Arc<WakeJobs>>, job_id: &str) -> Result<Json<WakeJob>, SystemError> {
+    let job = jobs.get(job_id)
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    Ok(Json(job))
+}
+
+// IP에 해당하는 MAC 주소를 이웃(ARP/NDP) 테이블에서 조회 (ping=true면 조회 전 장치에 패킷을 보내 테이블을 갱신)
+#[get("/neighbor/<ip>?<ping>")]
+pub async fn resolve_neighbor(_auth: AuthUser, wol_config: &State<WolConfig>, ip: &str, ping: Option<bool>)
+                              -> Result<Json<NeighborEntry>, SystemError> {
+    let ip = ip.parse::<std::net::IpAddr>()
+        .map_err(|_| SystemError::APIError(400, 0, "Invalid IP address".to_string()))?;
+    let entry = resolve_mac(ip, ping.unwrap_or(true), Duration::from_millis(wol_config.probe_timeout_ms)).await?
+        .ok_or(SystemError::APIError(404, 0, format!("No neighbor entry found for {}", ip)))?;
+    Ok(Json(entry))
+}
+
+// MAC 주소의 제조사와 주소 종류(로컬 관리, 멀티캐스트) 조회
+#[get("/mac/<mac>")]
+pub async fn lookup_mac(_auth: AuthUser, mac: &str) -> Result<Json<MacInfo>, SystemError> {
+    Ok(Json(mac_info(mac)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: &str) -> PostDeviceReq {
+        rocket::serde::json::from_str(json).unwrap()
+    }
+
+    fn device() -> Device {
+        Device {
+            mac: "00:11:22:33:44:55".to_string(),
+            broadcast: Some("192.168.0.255".to_string()),
+            port: 7,
+            interface: Some("eth0".to_string()),
+            transport: WakeTransport::Both.as_str().to_string(),
+            vlan_id: Some(10),
+            sleep_on_lan: true,
+            ssh_user: Some("admin".to_string()),
+            bmc_insecure: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn omitted_fields_keep_stored_values() {
+        let crypto = CryptoConfig { encryption_key: "0".repeat(64) };
+        let mut device = device();
+        apply_device_request(&mut device, &request(r#"{"name": "pc", "ip": "192.168.0.10"}"#), &crypto).unwrap();
+        assert_eq!(device.broadcast.as_deref(), Some("192.168.0.255"));
+        assert_eq!(device.port, 7);
+        assert_eq!(device.interface.as_deref(), Some("eth0"));
+        assert_eq!(device.transport, "both");
+        assert_eq!(device.vlan_id, Some(10));
+        assert!(device.sleep_on_lan);
+        assert_eq!(device.ssh_user.as_deref(), Some("admin"));
+        assert!(device.bmc_insecure);
+    }
+
+    #[test]
+    fn null_or_empty_fields_clear_stored_values() {
+        let crypto = CryptoConfig { encryption_key: "0".repeat(64) };
+        let mut device = device();
+        let req = request(r#"{"name": "pc", "ip": "192.168.0.10", "broadcast": "", "port": null, "interface": null,
+                              "transport": null, "vlan_id": null, "sleep_on_lan": null, "ssh_user": " ",
+                              "bmc_insecure": null}"#);
+        apply_device_request(&mut device, &req, &crypto).unwrap();
+        assert_eq!(device.broadcast, None);
+        assert_eq!(device.port, DEFAULT_WOL_PORT as i64);
+        assert_eq!(device.interface, None);
+        assert_eq!(device.transport, "udp");
+        assert_eq!(device.vlan_id, None);
+        assert!(!device.sleep_on_lan);
+        assert_eq!(device.ssh_user, None);
+        assert!(!device.bmc_insecure);
+    }
+
+    #[test]
+    fn clearing_interface_of_ethernet_device_is_rejected() {
+        let crypto = CryptoConfig { encryption_key: "0".repeat(64) };
+        let mut device = device();
+        let req = request(r#"{"name": "pc", "ip": "192.168.0.10", "interface": null}"#);
+        assert!(apply_device_request(&mut device, &req, &crypto).is_err());
+    }
 }
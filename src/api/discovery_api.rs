@@ -0,0 +1,119 @@
+use rocket::serde::{json::Json, Deserialize};
+use rocket::http::Status;
+use rocket::State;
+use validator::Validate;
+use crate::api::{map_duplicated, validate_request};
+use crate::api::device_api::{check_mac_bits, validate_ip_address, validate_mac_address};
+use crate::config::DiscoveryConfig;
+use crate::db::Db;
+use crate::db::device::{Device, DeviceOperations};
+use crate::error::SystemError;
+use crate::module::auth::AuthUser;
+use crate::module::discovery::{interface_networks, scan, DiscoveredHost, Ipv4Network};
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::power::PowerDriverKind;
+use crate::module::magic_packet::{normalize_mac_address, DEFAULT_WOL_PORT};
+
+// CIDR 검증 함수 (빈 값은 인터페이스 대역 사용으로 취급)
+fn validate_cidr(cidr: &str) -> Result<(), validator::ValidationError> {
+    if cidr.trim().is_empty() || Ipv4Network::parse(cidr).is_ok() {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_cidr"))
+    }
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ScanReq {
+    // 미지정시 호스트의 네트워크 인터페이스 대역 (가상 인터페이스 제외, 큰 대역은 max_hosts 이내로 줄임)
+    #[validate(custom(function = "validate_cidr", message = "올바른 CIDR 형식이 아닙니다 (예: 192.168.0.0/24)"))]
+    pub cidr: Option<String>,
+}
+
+// 네트워크를 검색하여 아직 등록되지 않은 장치 후보 목록 반환
+#[post("/discovery/scan", data = "<req>")]
+pub async fn scan_network(db: &Db, _auth: AuthUser, config: &State<DiscoveryConfig>, req: Json<ScanReq>)
+                          -> Result<Json<Vec<DiscoveredHost>>, SystemError> {
+    validate_request(&*req)?;
+
+    let networks = match req.cidr.as_deref().map(str::trim).filter(|cidr| !cidr.is_empty()) {
+        Some(cidr) => vec![Ipv4Network::parse(cidr)?],
+        None => interface_networks(config.max_hosts)?,
+    };
+    if networks.is_empty() {
+        return Err(SystemError::APIError(400, 0, "No IPv4 network interface found to scan".to_string()));
+    }
+
+    let devices = Device::get_all(db).await?;
+    let hosts = scan(&networks, config).await?;
+    let candidates = hosts.into_iter()
+        .filter(|host| !devices.iter().any(|device| {
+            device.ip == host.ip || host.mac.as_ref() == Some(&device.mac)
+        }))
+        .collect();
+    Ok(Json(candidates))
+}
+
+// 장치 등록(POST /device)과 같은 기준으로 멀티캐스트/로컬 관리 MAC 주소 확인
+fn validate_import_mac_bits(req: &ImportDeviceReq) -> Result<(), validator::ValidationError> {
+    check_mac_bits(&req.mac, req.allow_local_mac.unwrap_or(false))
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+#[validate(schema(function = "validate_import_mac_bits"))]
+pub struct ImportDeviceReq {
+    #[validate(length(min = 1, message = "이름은 공백일 수 없습니다"))]
+    pub name: String,
+    #[validate(custom(function = "validate_mac_address", message = "올바른 MAC 주소 형식이 아닙니다 (예: AA:BB:CC:DD:EE:FF, aabb.ccdd.eeff)"))]
+    pub mac: String,
+    // 로컬 관리(U/L 비트) MAC 주소 허용 여부
+    pub allow_local_mac: Option<bool>,
+    #[validate(custom(function = "validate_ip_address", message = "올바른 IP 주소 형식이 아닙니다"))]
+    pub ip: String,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ImportReq {
+    #[validate(nested)]
+    pub devices: Vec<ImportDeviceReq>,
+}
+
+// 검색 결과에서 선택한 장치들을 목록 끝에 순서대로 추가
+#[post("/discovery/import", data = "<req>")]
+pub async fn import_devices(db: &Db, _auth: AuthUser, events: &State<EventBus>, req: Json<ImportReq>)
+                            -> Result<Status, SystemError> {
+    validate_request(&*req)?;
+    if req.devices.is_empty() {
+        return Err(SystemError::APIError(400, 0, "No devices selected to import".to_string()));
+    }
+    if req.devices.iter().any(|device| device.mac.trim().is_empty()) {
+        return Err(SystemError::APIError(400, 0, "MAC address is required for every imported device".to_string()));
+    }
+
+    // 하나라도 실패하면(중복 MAC 등) 모두 취소
+    let mut order_num = Device::get_max_order_num(&db.0).await?;
+    let mut tx = db.begin().await?;
+    let mut created = Vec::with_capacity(req.devices.len());
+    for import in &req.devices {
+        order_num += 1;
+        let device = Device::insert(&mut tx, &Device {
+            name: import.name.trim().to_string(),
+            mac: normalize_mac_address(&import.mac)?,
+            ip: import.ip.trim().to_string(),
+            order_num,
+            port: DEFAULT_WOL_PORT as i64,
+            transport: "udp".to_string(),
+            power_driver: PowerDriverKind::Wol.as_str().to_string(),
+            ..Default::default()
+        }).await.map_err(map_duplicated)?;
+        created.push(device.id);
+    }
+    tx.commit().await?;
+    for device_id in created {
+        events.publish(DeviceEvent::DeviceCreated { device_id });
+    }
+    Ok(Status::Ok)
+}
@@ -0,0 +1,29 @@
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::{Shutdown, State};
+use crate::module::auth::AuthUser;
+use crate::module::events::EventBus;
+
+// 장치 변경, 웨이크, 상태 변경 이벤트를 SSE(text/event-stream)로 전달
+#[get("/events")]
+pub fn events(_auth: AuthUser, bus: &State<EventBus>, mut shutdown: Shutdown) -> EventStream![] {
+    let mut rx = bus.subscribe();
+    EventStream! {
+        loop {
+            let event = select! {
+                event = rx.recv() => match event {
+                    Ok(event) => event,
+                    // 누락된 이벤트가 있으면 클라이언트가 전체 목록을 다시 불러오도록 알림
+                    Err(RecvError::Lagged(_)) => {
+                        yield Event::data("{}").event("resync");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            };
+            yield Event::json(&event).event(event.name());
+        }
+    }
+}
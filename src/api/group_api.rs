@@ -0,0 +1,114 @@
+use rocket::serde::{json::Json, Deserialize, Serialize};
+use rocket::http::Status;
+use rocket::response::status::Accepted;
+use std::time::Duration;
+use rocket::State;
+use validator::Validate;
+use crate::api::{map_duplicated, validate_request};
+use crate::module::auth::AuthUser;
+use crate::db::Db;
+use crate::db::device::{Device, DeviceOperations, MoveDirection};
+use crate::db::group::{DeviceGroup, GroupOperations};
+use crate::error::{PredefinedApiError, SystemError};
+use crate::module::events::EventBus;
+use crate::module::group_wake::start_group_wake;
+use crate::module::power::PowerResources;
+
+// 그룹 웨이크 대기 시간 최대값
+const MAX_WAKE_STAGGER_MS: u64 = 600_000;
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct PostGroupReq {
+    pub id: Option<i64>,
+    #[validate(length(min = 1, max = 64, message = "이름은 1~64자여야 합니다"))]
+    pub name: String,
+    // 미지정시 Rocket.toml의 [wol] group_stagger_ms 사용
+    #[validate(range(max = MAX_WAKE_STAGGER_MS, message = "대기 시간은 0~600000ms 사이여야 합니다"))]
+    pub wake_stagger_ms: Option<u64>,
+}
+
+#[get("/groups")]
+pub async fn get_groups(db: &Db, _auth: AuthUser) -> Result<Json<Vec<DeviceGroup>>, SystemError> {
+    Ok(Json(DeviceGroup::get_all(db).await?))
+}
+
+#[post("/group", data = "<req>")]
+pub async fn create_group(db: &Db, _auth: AuthUser, req: Json<PostGroupReq>) -> Result<Status, SystemError> {
+    validate_request(&*req)?;
+
+    let max_order = DeviceGroup::get_max_order_num(db).await?;
+    let group = DeviceGroup {
+        name: req.name.trim().to_string(),
+        order_num: max_order + 1,
+        wake_stagger_ms: req.wake_stagger_ms.map(|ms| ms as i64),
+        ..Default::default()
+    };
+    DeviceGroup::insert(db, &group).await.map_err(map_duplicated)?;
+    Ok(Status::Ok)
+}
+
+#[put("/group", data = "<req>")]
+pub async fn update_group(db: &Db, _auth: AuthUser, req: Json<PostGroupReq>) -> Result<Status, SystemError> {
+    validate_request(&*req)?;
+
+    let id = req.id.ok_or(PredefinedApiError::InvalidRequest.get())?;
+    let mut group = DeviceGroup::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    group.name = req.name.trim().to_string();
+    group.wake_stagger_ms = req.wake_stagger_ms.map(|ms| ms as i64);
+    group.update(db).await.map_err(map_duplicated)?;
+    Ok(Status::Ok)
+}
+
+// 그룹을 삭제해도 소속 장치는 유지됨 (그룹 미지정으로 변경)
+#[delete("/group/<id>")]
+pub async fn delete_group(db: &Db, _auth: AuthUser, id: i64) -> Result<Status, SystemError> {
+    DeviceGroup::delete(db, id).await?;
+    Ok(Status::Ok)
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct MoveGroupReq {
+    pub id: i64,
+    pub direction: String, // "up" or "down"
+}
+
+#[put("/group/move", data = "<req>")]
+pub async fn move_group(db: &Db, _auth: AuthUser, req: Json<MoveGroupReq>) -> Result<Status, SystemError> {
+    let direction = MoveDirection::parse(&req.direction)
+        .ok_or(SystemError::APIError(400, 0, "Invalid direction. Use 'up' or 'down'".to_string()))?;
+
+    DeviceGroup::move_order(db, req.id, direction).await?;
+    Ok(Status::Ok)
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct GroupWakeRes {
+    pub group_id: i64,
+    // 켜는 순서대로 나열된 장치
+    pub device_ids: Vec<i64>,
+    pub stagger_ms: u64,
+}
+
+// 그룹의 모든 장치를 장치 순서대로 각자의 전원 드라이버로 켬 (결과는 group_wake 이벤트로 전달)
+// 장치에 의존 장치가 있다면 의존 장치를 먼저 켜고 응답을 확인
+// 대기 시간은 stagger_ms 파라미터, 그룹 설정, Rocket.toml 기본값 순으로 적용
+#[post("/group/<id>/wake?<stagger_ms>")]
+pub async fn wake_group(db: &Db, _auth: AuthUser, power: &State<PowerResources>, events: &State<EventBus>,
+                        id: i64, stagger_ms: Option<u64>)
+                        -> Result<Accepted<Json<GroupWakeRes>>, SystemError> {
+    let group = DeviceGroup::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let stagger_ms = stagger_ms
+        .or(group.wake_stagger_ms.and_then(|ms| u64::try_from(ms).ok()))
+        .unwrap_or(power.wol.group_stagger_ms)
+        .min(MAX_WAKE_STAGGER_MS);
+    let devices = Device::get_by_group(db, id).await?;
+    let device_ids = devices.iter().map(|device| device.id).collect();
+
+    start_group_wake(power.inner().clone(), events.inner().clone(), id, devices, Duration::from_millis(stagger_ms));
+    Ok(Accepted(Json(GroupWakeRes { group_id: id, device_ids, stagger_ms })))
+}
@@ -0,0 +1,216 @@
+use rocket::serde::{json::Json, Deserialize, Serialize};
+use rocket::http::Status;
+use rocket::response::status::Accepted;
+use rocket::State;
+use validator::Validate;
+use sqlx::SqliteConnection;
+use crate::api::{map_duplicated, none_if_empty, validate_request};
+use crate::module::auth::AuthUser;
+use crate::db::Db;
+use crate::db::device::{Device, DeviceOperations};
+use crate::db::scene::{Scene, SceneOperations, SceneRun, SceneRunLog, SceneRunLogOperations, SceneRunOperations, SceneStep,
+                       SceneStepOperations};
+use crate::error::{PredefinedApiError, SystemError};
+use crate::module::events::EventBus;
+use crate::module::power::PowerResources;
+use crate::module::scene::{start_scene_run, SceneStepKind};
+
+// wait 단계 최대 대기 시간
+const MAX_STEP_SECONDS: u32 = 86_400;
+const DEFAULT_RUN_LIMIT: i64 = 20;
+
+// 단계 종류별 필수 항목 검증
+fn validate_scene_step(step: &PostSceneStepReq) -> Result<(), validator::ValidationError> {
+    let error = |message: &'static str| Err(validator::ValidationError::new("invalid_scene_step").with_message(message.into()));
+    let Some(kind) = SceneStepKind::parse(&step.kind) else {
+        return error("단계 종류는 wake, wait, wait_online, run_action, shutdown, notify 중 하나여야 합니다");
+    };
+    if kind.requires_device() && step.device_id.is_none() {
+        return error("장치를 지정해야 합니다");
+    }
+    match kind {
+        SceneStepKind::Wait if step.seconds.is_none() => error("대기 시간을 지정해야 합니다"),
+        SceneStepKind::RunAction if none_if_empty(&step.action_name).is_none() => error("실행할 동작 이름을 지정해야 합니다"),
+        SceneStepKind::Notify if none_if_empty(&step.message).is_none() => error("알림 내용을 지정해야 합니다"),
+        SceneStepKind::Notify if none_if_empty(&step.url)
+            .is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://")) =>
+            error("웹훅 URL은 http:// 또는 https://로 시작해야 합니다"),
+        _ => Ok(()),
+    }
+}
+
+fn validate_scene(scene: &PostSceneReq) -> Result<(), validator::ValidationError> {
+    if scene.steps.is_empty() {
+        Err(validator::ValidationError::new("empty_steps").with_message("단계가 1개 이상 필요합니다".into()))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+#[validate(schema(function = "validate_scene"))]
+pub struct PostSceneReq {
+    pub id: Option<i64>,
+    #[validate(length(min = 1, max = 64, message = "이름은 1~64자여야 합니다"))]
+    pub name: String,
+    // 목록 순서대로 실행
+    #[validate(nested)]
+    pub steps: Vec<PostSceneStepReq>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+#[validate(schema(function = "validate_scene_step"))]
+pub struct PostSceneStepReq {
+    // wake, wait, wait_online, run_action, shutdown, notify
+    pub kind: String,
+    pub device_id: Option<i64>,
+    // wait: 대기 시간, wait_online: 최대 대기 시간 (미지정시 Rocket.toml의 verify_timeout_secs)
+    #[validate(range(min = 1, max = MAX_STEP_SECONDS, message = "대기 시간은 1~86400초 사이여야 합니다"))]
+    pub seconds: Option<u32>,
+    pub action_name: Option<String>,
+    pub message: Option<String>,
+    // notify 단계의 웹훅 주소
+    pub url: Option<String>,
+}
+
+impl From<&PostSceneStepReq> for SceneStep {
+    fn from(req: &PostSceneStepReq) -> Self {
+        let kind = SceneStepKind::parse(&req.kind).unwrap_or(SceneStepKind::Wait);
+        Self {
+            kind: kind.as_str().to_string(),
+            device_id: if kind.requires_device() { req.device_id } else { None },
+            seconds: match kind {
+                SceneStepKind::Wait | SceneStepKind::WaitOnline => req.seconds.map(i64::from),
+                _ => None,
+            },
+            action_name: if kind == SceneStepKind::RunAction { none_if_empty(&req.action_name) } else { None },
+            message: if kind == SceneStepKind::Notify { none_if_empty(&req.message) } else { None },
+            url: if kind == SceneStepKind::Notify { none_if_empty(&req.url) } else { None },
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct GetSceneRes {
+    pub id: i64,
+    pub name: String,
+    pub steps: Vec<SceneStep>,
+}
+
+impl GetSceneRes {
+    fn new(scene: Scene, steps: &[SceneStep]) -> Self {
+        Self {
+            steps: steps.iter().filter(|step| step.scene_id == scene.id).cloned().collect(),
+            id: scene.id,
+            name: scene.name,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct SceneRunRes {
+    #[serde(flatten)]
+    pub run: SceneRun,
+    pub logs: Vec<SceneRunLog>,
+}
+
+#[get("/scenes")]
+pub async fn get_scenes(db: &Db, _auth: AuthUser) -> Result<Json<Vec<GetSceneRes>>, SystemError> {
+    let scenes = Scene::get_all(db).await?;
+    let steps = SceneStep::get_all(db).await?;
+    Ok(Json(scenes.into_iter().map(|scene| GetSceneRes::new(scene, &steps)).collect()))
+}
+
+#[get("/scene/<id>")]
+pub async fn get_scene(db: &Db, _auth: AuthUser, id: i64) -> Result<Json<GetSceneRes>, SystemError> {
+    let scene = Scene::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let steps = SceneStep::get_by_scene(db, id).await?;
+    Ok(Json(GetSceneRes::new(scene, &steps)))
+}
+
+// 단계에 지정된 장치가 존재하는지 확인
+async fn check_scene_devices(db: &Db, req: &PostSceneReq) -> Result<(), SystemError> {
+    for device_id in req.steps.iter().filter_map(|step| step.device_id) {
+        if Device::get(db, device_id).await?.is_none() {
+            return Err(SystemError::APIError(400, 0, format!("Device not found: {}", device_id)));
+        }
+    }
+    Ok(())
+}
+
+async fn save_steps(conn: &mut SqliteConnection, scene_id: i64, req: &PostSceneReq) -> Result<(), SystemError> {
+    let steps: Vec<SceneStep> = req.steps.iter().map(SceneStep::from).collect();
+    SceneStep::save_for_scene(conn, scene_id, &steps).await?;
+    Ok(())
+}
+
+#[post("/scene", data = "<req>")]
+pub async fn create_scene(db: &Db, _auth: AuthUser, req: Json<PostSceneReq>) -> Result<Status, SystemError> {
+    validate_request(&*req)?;
+    check_scene_devices(db, &req).await?;
+
+    // 씬과 단계를 함께 저장 (단계 저장에 실패하면 빈 씬이 남지 않도록 모두 취소)
+    let mut tx = db.begin().await?;
+    let scene = Scene::insert(&mut tx, &Scene { name: req.name.trim().to_string(), ..Default::default() }).await
+        .map_err(map_duplicated)?;
+    save_steps(&mut tx, scene.id, &req).await?;
+    tx.commit().await?;
+    Ok(Status::Ok)
+}
+
+#[put("/scene", data = "<req>")]
+pub async fn update_scene(db: &Db, _auth: AuthUser, req: Json<PostSceneReq>) -> Result<Status, SystemError> {
+    validate_request(&*req)?;
+    check_scene_devices(db, &req).await?;
+
+    let id = req.id.ok_or(PredefinedApiError::InvalidRequest.get())?;
+    let mut scene = Scene::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    scene.name = req.name.trim().to_string();
+    let mut tx = db.begin().await?;
+    scene.update(&mut tx).await.map_err(map_duplicated)?;
+    save_steps(&mut tx, scene.id, &req).await?;
+    tx.commit().await?;
+    Ok(Status::Ok)
+}
+
+#[delete("/scene/<id>")]
+pub async fn delete_scene(db: &Db, _auth: AuthUser, id: i64) -> Result<Status, SystemError> {
+    Scene::delete(db, id).await?;
+    Ok(Status::Ok)
+}
+
+// 씬을 백그라운드로 실행 (진행 상황은 /scene-run/<run_id> 또는 scene_run 이벤트로 확인)
+#[post("/scene/<id>/run")]
+pub async fn run_scene(db: &Db, _auth: AuthUser, power: &State<PowerResources>, events: &State<EventBus>, id: i64)
+                       -> Result<Accepted<Json<SceneRun>>, SystemError> {
+    let scene = Scene::get(db, id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let steps = SceneStep::get_by_scene(db, id).await?;
+    let run = SceneRun::insert(db, id).await?;
+
+    start_scene_run(power.inner().clone(), events.inner().clone(), scene, steps, run.clone());
+    Ok(Accepted(Json(run)))
+}
+
+// 씬의 실행 기록 (최근 순)
+#[get("/scene/<id>/runs?<limit>")]
+pub async fn get_scene_runs(db: &Db, _auth: AuthUser, id: i64, limit: Option<i64>) -> Result<Json<Vec<SceneRun>>, SystemError> {
+    let limit = limit.unwrap_or(DEFAULT_RUN_LIMIT).clamp(1, 1000);
+    Ok(Json(SceneRun::get_by_scene(db, id, limit).await?))
+}
+
+// 실행 하나의 상태와 단계별 기록
+#[get("/scene-run/<run_id>")]
+pub async fn get_scene_run(db: &Db, _auth: AuthUser, run_id: i64) -> Result<Json<SceneRunRes>, SystemError> {
+    let run = SceneRun::get(db, run_id).await?
+        .ok_or(PredefinedApiError::NotFound.get())?;
+    let logs = SceneRunLog::get_by_run(db, run_id).await?;
+    Ok(Json(SceneRunRes { run, logs }))
+}
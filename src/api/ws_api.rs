@@ -0,0 +1,250 @@
+use std::sync::Arc;
+use rocket::futures::future::join_all;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::http::Status;
+use rocket::outcome::Outcome::{Error, Success};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::tokio::sync::broadcast::Receiver;
+use rocket::tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use rocket::State;
+use rocket_ws::{Channel, Message, WebSocket};
+use serde_json::{json, Value};
+use crate::config::CorsConfig;
+use crate::db::device::{Device, DeviceOperations, MoveDirection};
+use crate::error::{PredefinedApiError, SystemError};
+use crate::module::auth::AuthUser;
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::monitor::refresh_device;
+use crate::module::power::PowerResources;
+use crate::module::wake_job::{start_wake, WakeJobs};
+
+// 클라이언트가 보내는 명령 ({"id": "1", "command": "wake", "device_id": 3})
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde", tag = "command", rename_all = "snake_case")]
+enum WsCommand {
+    // 장치 이벤트 수신 시작/중지
+    Subscribe,
+    Unsubscribe,
+    Wake { device_id: i64 },
+    Move { device_id: i64, direction: String },
+    // 온라인 여부를 즉시 확인 (device_id 미지정시 전체 장치)
+    RefreshStatus { device_id: Option<i64> },
+}
+
+// 서버가 보내는 메시지
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde", tag = "type", rename_all = "snake_case")]
+enum WsMessage {
+    // 명령별 처리 결과 (id는 요청의 id를 그대로 반환)
+    Ack { id: Option<Value>, ok: bool, result: Option<Value>, error: Option<String> },
+    Event { event: DeviceEvent },
+    // 누락된 이벤트가 있으므로 전체 목록을 다시 불러와야 함
+    Resync,
+}
+
+// 명령 처리에 필요한 상태 (요청 수명과 무관하게 연결이 유지되므로 복제하여 보관)
+#[derive(Clone)]
+struct WsContext {
+    resources: PowerResources,
+    events: EventBus,
+    jobs: Arc<WakeJobs>,
+}
+
+// 업그레이드 요청의 Origin 확인 (쿠키 인증을 쓰므로 다른 사이트에서 연결을 열지 못하도록 함)
+// Origin이 없는 요청(브라우저 외 클라이언트), 같은 출처, [cors] allow_origin에 지정된 출처만 허용
+pub struct WsOrigin;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WsOrigin {
+    type Error = SystemError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, SystemError> {
+        let Some(origin) = request.headers().get_one("Origin") else {
+            return Success(WsOrigin);
+        };
+        let cors_config = request.rocket().state::<CorsConfig>().cloned().unwrap_or_default();
+        let host = request.headers().get_one("Host");
+        if is_allowed_origin(origin, host, &cors_config) {
+            Success(WsOrigin)
+        } else {
+            Error((Status::Forbidden, SystemError::APIError(403, 0, format!("Origin not allowed: {}", origin))))
+        }
+    }
+}
+
+fn is_allowed_origin(origin: &str, host: Option<&str>, cors_config: &CorsConfig) -> bool {
+    let origin = origin.trim_end_matches('/');
+    let same_origin = host.is_some_and(|host| origin.split_once("://")
+        .is_some_and(|(_, origin_host)| origin_host.eq_ignore_ascii_case(host)));
+    same_origin || cors_config.allow_origin == "*" || cors_config.allow_origin.trim_end_matches('/').eq_ignore_ascii_case(origin)
+}
+
+// 장치 이벤트 구독과 명령 전송을 위한 WebSocket 채널
+// 인증은 업그레이드 요청의 쿠키로 AuthUser와 동일하게 처리 (accessToken 만료시 재발급 포함)
+#[get("/ws")]
+pub fn control_channel(ws: WebSocket, _origin: WsOrigin, _auth: AuthUser, power: &State<PowerResources>,
+                       events: &State<EventBus>, jobs: &State<Arc<WakeJobs>>) -> Channel<'static> {
+    let ctx = WsContext {
+        resources: power.inner().clone(),
+        events: events.inner().clone(),
+        jobs: Arc::clone(jobs),
+    };
+
+    ws.channel(move |mut stream| Box::pin(async move {
+        // 구독 중일 때만 수신기를 유지 (구독 전의 이벤트가 쌓이지 않도록)
+        let mut rx: Option<Receiver<DeviceEvent>> = None;
+        // 별도 작업으로 실행한 명령의 처리 결과
+        let (reply_tx, mut replies) = unbounded_channel();
+
+        loop {
+            let reply = select! {
+                message = stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => match handle_message(&ctx, &text, &mut rx, &reply_tx) {
+                        Some(reply) => reply,
+                        None => continue,
+                    },
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e),
+                },
+                reply = replies.recv() => match reply {
+                    Some(reply) => reply,
+                    None => break,
+                },
+                event = next_event(&mut rx) => match event {
+                    Ok(event) => WsMessage::Event { event },
+                    Err(RecvError::Lagged(_)) => WsMessage::Resync,
+                    Err(RecvError::Closed) => break,
+                },
+            };
+            let text = serde_json::to_string(&reply).unwrap_or_default();
+            stream.send(Message::Text(text)).await?;
+        }
+        Ok(())
+    }))
+}
+
+// 구독하지 않은 상태에서는 이벤트를 기다리지 않음
+async fn next_event(rx: &mut Option<Receiver<DeviceEvent>>) -> Result<DeviceEvent, RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+// 구독 명령은 바로 처리하여 응답을 반환하고, 그 외 명령은 별도 작업으로 실행하여 응답을 replies로 전달
+// (웨이크나 상태 확인이 끝날 때까지 이벤트 전달과 다른 명령 처리가 멈추지 않도록 함)
+fn handle_message(ctx: &WsContext, text: &str, rx: &mut Option<Receiver<DeviceEvent>>,
+                  replies: &UnboundedSender<WsMessage>) -> Option<WsMessage> {
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => return Some(ack(None, Err(SystemError::APIError(400, 0, format!("Invalid message: {}", e))))),
+    };
+    let id = value.get("id").cloned();
+    let command: WsCommand = match serde_json::from_value(value) {
+        Ok(command) => command,
+        Err(e) => return Some(ack(id, Err(SystemError::APIError(400, 0, format!("Invalid command: {}", e))))),
+    };
+
+    match command {
+        // 구독할 때마다 새 수신기를 만들어 이전 구독의 밀린 이벤트를 버림
+        WsCommand::Subscribe => {
+            *rx = Some(ctx.events.subscribe());
+            Some(ack(id, Ok(Value::Null)))
+        }
+        WsCommand::Unsubscribe => {
+            *rx = None;
+            Some(ack(id, Ok(Value::Null)))
+        }
+        command => {
+            let ctx = ctx.clone();
+            let replies = replies.clone();
+            rocket::tokio::spawn(async move {
+                let result = run_command(&ctx, command).await;
+                // 연결이 이미 닫혔다면 응답을 버림
+                let _ = replies.send(ack(id, result));
+            });
+            None
+        }
+    }
+}
+
+async fn run_command(ctx: &WsContext, command: WsCommand) -> Result<Value, SystemError> {
+    match command {
+        WsCommand::Wake { device_id } => wake_command(ctx, device_id).await,
+        WsCommand::Move { device_id, direction } => move_command(ctx, device_id, &direction).await,
+        WsCommand::RefreshStatus { device_id } => refresh_command(ctx, device_id).await,
+        // 구독 명령은 handle_message에서 처리
+        WsCommand::Subscribe | WsCommand::Unsubscribe => Ok(Value::Null),
+    }
+}
+
+fn ack(id: Option<Value>, result: Result<Value, SystemError>) -> WsMessage {
+    match result {
+        Ok(result) => WsMessage::Ack { id, ok: true, result: Some(result).filter(|r| !r.is_null()), error: None },
+        Err(e) => WsMessage::Ack { id, ok: false, result: None, error: Some(e.message()) },
+    }
+}
+
+async fn get_device(ctx: &WsContext, device_id: i64) -> Result<Device, SystemError> {
+    Device::get(&ctx.resources.pool, device_id).await?
+        .ok_or(PredefinedApiError::NotFound.get())
+}
+
+async fn wake_command(ctx: &WsContext, device_id: i64) -> Result<Value, SystemError> {
+    let device = get_device(ctx, device_id).await?;
+    let wake = start_wake(Arc::clone(&ctx.jobs), ctx.resources.clone(), device).await?;
+    Ok(json!(wake))
+}
+
+async fn move_command(ctx: &WsContext, device_id: i64, direction: &str) -> Result<Value, SystemError> {
+    let direction = MoveDirection::parse(direction)
+        .ok_or(SystemError::APIError(400, 0, "Invalid direction. Use 'up' or 'down'".to_string()))?;
+    let moved = Device::move_order(&ctx.resources.pool, device_id, direction).await?;
+    if moved {
+        ctx.events.publish(DeviceEvent::DeviceMoved { device_id });
+    }
+    Ok(json!({ "moved": moved }))
+}
+
+async fn refresh_command(ctx: &WsContext, device_id: Option<i64>) -> Result<Value, SystemError> {
+    let devices = match device_id {
+        Some(device_id) => vec![get_device(ctx, device_id).await?],
+        None => Device::get_all(&ctx.resources.pool).await?,
+    };
+
+    let checks = devices.iter().map(|device| refresh_device(&ctx.resources.pool, &ctx.resources.monitor, &ctx.events, device));
+    let mut statuses = Vec::new();
+    for (device, status) in devices.iter().zip(join_all(checks).await) {
+        statuses.push(json!({ "device_id": device.id, "status": status?.as_str() }));
+    }
+    Ok(Value::Array(statuses))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cors(allow_origin: &str) -> CorsConfig {
+        CorsConfig { allow_origin: allow_origin.to_string(), allow_credentials: true }
+    }
+
+    #[test]
+    fn allows_same_origin_and_configured_origin() {
+        let config = cors("http://192.168.0.2:5173");
+        assert!(is_allowed_origin("http://wol.local:8000", Some("wol.local:8000"), &config));
+        assert!(is_allowed_origin("http://192.168.0.2:5173", Some("wol.local:8000"), &config));
+        assert!(is_allowed_origin("http://evil.example", None, &cors("*")));
+    }
+
+    #[test]
+    fn rejects_other_origins() {
+        let config = cors("http://192.168.0.2:5173");
+        assert!(!is_allowed_origin("http://evil.example", Some("wol.local:8000"), &config));
+        assert!(!is_allowed_origin("http://wol.local:8001", Some("wol.local:8000"), &config));
+        assert!(!is_allowed_origin("null", Some("wol.local:8000"), &config));
+    }
+}
@@ -34,5 +34,139 @@ impl Default for CorsConfig {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct CryptoConfig {
+    pub encryption_key: String,
+}
+
+impl Default for CryptoConfig {
+    fn default() -> Self {
+        Self {
+            encryption_key: "generate".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct WolConfig {
+    // true면 매직패킷을 실제로 전송하지 않고 기록만 함
+    pub dry_run: bool,
+    // 패킷 1개 전송 타임아웃
+    pub send_timeout_ms: u64,
+    // 장치에 별도 설정이 없을 때 사용하는 전송 정책
+    pub burst_count: u32,
+    pub burst_interval_ms: u64,
+    // 전송 대상 (broadcast: 255.255.255.255, subnet: 서브넷 브로드캐스트, unicast: 장치 IP)
+    pub targets: Vec<String>,
+    // 웨이크 후 장치 응답 확인 (wake-and-verify)
+    pub verify_timeout_secs: u64,
+    pub verify_interval_secs: u64,
+    // ICMP/TCP 확인 1회의 타임아웃
+    pub probe_timeout_ms: u64,
+    // 그룹 웨이크시 장치 사이의 대기 시간 (동시 기동으로 차단기가 내려가지 않도록)
+    pub group_stagger_ms: u64,
+}
+
+impl Default for WolConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            send_timeout_ms: 1000,
+            burst_count: 3,
+            burst_interval_ms: 100,
+            targets: vec!["subnet".to_string()],
+            verify_timeout_secs: 180,
+            verify_interval_secs: 3,
+            probe_timeout_ms: 1000,
+            group_stagger_ms: 2000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct MonitorConfig {
+    pub enabled: bool,
+    // 전체 장치 상태 확인 주기
+    pub interval_secs: u64,
+    pub timeout_ms: u64,
+    // ICMP 응답이 없거나 ICMP를 사용할 수 없을 때 확인할 TCP 포트
+    pub tcp_fallback_ports: Vec<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct SshConfig {
+    // 장치에 명령이 지정되지 않았을 때 실행할 명령
+    pub default_command: String,
+    pub connect_timeout_ms: u64,
+    // 명령 실행 후 종료를 기다리는 최대 시간 (종료 명령은 연결이 먼저 끊길 수 있음)
+    pub command_timeout_secs: u64,
+    // 응답에 포함하는 출력의 최대 길이
+    pub max_output_bytes: usize,
+}
+
+impl Default for SshConfig {
+    fn default() -> Self {
+        Self {
+            default_command: "shutdown -h now".to_string(),
+            connect_timeout_ms: 5000,
+            command_timeout_secs: 30,
+            max_output_bytes: 64 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct IpmiConfig {
+    // IPMI 드라이버가 실행하는 ipmitool 경로 (장치 설정으로는 지정할 수 없음)
+    pub ipmitool: String,
+}
+
+impl Default for IpmiConfig {
+    fn default() -> Self {
+        Self {
+            ipmitool: "ipmitool".to_string(),
+        }
+    }
+}
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct DiscoveryConfig {
+    // 한 번의 검색에서 확인하는 최대 호스트 수 (큰 대역 지정 방지)
+    pub max_hosts: usize,
+    // 동시에 확인하는 호스트 수
+    pub concurrency: usize,
+    pub timeout_ms: u64,
+    // ICMP 응답이 없을 때 확인할 TCP 포트
+    pub tcp_ports: Vec<u16>,
+    // 역방향 DNS로 이름을 찾지 못한 호스트에 mDNS 질의
+    pub mdns: bool,
+}
 
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            max_hosts: 1024,
+            concurrency: 128,
+            timeout_ms: 500,
+            tcp_ports: vec![22, 80, 135, 443, 445, 3389],
+            mdns: true,
+        }
+    }
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 30,
+            timeout_ms: 1000,
+            tcp_fallback_ports: vec![22, 80, 135, 139, 443, 445, 3389],
+        }
+    }
+}
@@ -1,10 +1,18 @@
 pub mod user;
 pub(crate) mod token;
 pub mod device;
+pub mod probe;
+pub mod action;
+pub mod group;
+pub mod tag;
+pub mod dependency;
+pub mod scene;
 
 use rocket::serde::{Deserialize, Serialize};
 use rocket_db_pools::Database;
+use serde_json::{Map, Value};
 use crate::error::SystemError;
+use crate::module::magic_packet::normalize_mac_address;
 
 #[derive(Database)]
 #[database("sqlite")]
@@ -45,7 +53,7 @@ pub async fn create_tables(db: &Db) -> Result<(), SystemError> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             name VARCHAR(255) NOT NULL,
             mac VARCHAR(17) NOT NULL,
-            ip VARCHAR(20) NOT NULL,
+            ip VARCHAR(255) NOT NULL,
             order_num INTEGER NOT NULL
         )"#,
     )
@@ -53,4 +61,275 @@ pub async fn create_tables(db: &Db) -> Result<(), SystemError> {
         .await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// 기존 DB에도 적용되어야 하는 스키마 변경 (매 실행마다 수행되며, 이미 적용된 항목은 건너뜀)
+pub async fn migrate_tables(db: &Db) -> Result<(), SystemError> {
+    // 장치별 매직패킷 전송 설정
+    add_column_if_not_exists(db, "device", "broadcast", "VARCHAR(45)").await?;
+    add_column_if_not_exists(db, "device", "netmask", "VARCHAR(45)").await?;
+    add_column_if_not_exists(db, "device", "port", "INTEGER NOT NULL DEFAULT 9").await?;
+    add_column_if_not_exists(db, "device", "interface", "VARCHAR(255)").await?;
+    // SecureOn 비밀번호 (암호화하여 저장)
+    add_column_if_not_exists(db, "device", "secureon", "VARCHAR(255)").await?;
+    // 전송 방식 (udp, ethernet, both) 및 raw 이더넷 전송시 802.1Q VLAN 태그
+    add_column_if_not_exists(db, "device", "transport", "VARCHAR(10) NOT NULL DEFAULT 'udp'").await?;
+    add_column_if_not_exists(db, "device", "vlan_id", "INTEGER").await?;
+    // 장치별 전송 정책 (미지정시 설정 파일의 기본값 사용)
+    add_column_if_not_exists(db, "device", "burst_count", "INTEGER").await?;
+    add_column_if_not_exists(db, "device", "burst_interval_ms", "INTEGER").await?;
+    add_column_if_not_exists(db, "device", "wake_targets", "VARCHAR(255)").await?;
+    // 웨이크 후 응답 확인에 사용할 TCP 포트
+    add_column_if_not_exists(db, "device", "verify_port", "INTEGER").await?;
+    // 상태 모니터링 결과 (unknown, online, offline)
+    add_column_if_not_exists(db, "device", "status", "VARCHAR(10) NOT NULL DEFAULT 'unknown'").await?;
+    add_column_if_not_exists(db, "device", "last_seen", "datetime").await?;
+    // SSH 원격 종료 설정
+    add_column_if_not_exists(db, "device", "ssh_user", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "ssh_port", "INTEGER").await?;
+    add_column_if_not_exists(db, "device", "ssh_password", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "ssh_private_key", "TEXT").await?;
+    add_column_if_not_exists(db, "device", "ssh_command", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "ssh_host_key", "VARCHAR(255)").await?;
+    // Sleep-on-LAN 에이전트 실행 여부 (MAC을 뒤집은 매직패킷으로 절전)
+    add_column_if_not_exists(db, "device", "sleep_on_lan", "BOOLEAN NOT NULL DEFAULT 0").await?;
+    // 전원 제어 드라이버 (wol, redfish, ipmi, smart_plug) 및 드라이버별 설정 JSON
+    add_column_if_not_exists(db, "device", "power_driver", "VARCHAR(20) NOT NULL DEFAULT 'wol'").await?;
+    add_column_if_not_exists(db, "device", "power_config", "TEXT").await?;
+    // Redfish BMC 접속 정보 (비밀번호는 암호화하여 저장)
+    add_column_if_not_exists(db, "device", "bmc_url", "VARCHAR(2048)").await?;
+    add_column_if_not_exists(db, "device", "bmc_user", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "bmc_password", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "bmc_system_id", "VARCHAR(255)").await?;
+    add_column_if_not_exists(db, "device", "bmc_insecure", "BOOLEAN NOT NULL DEFAULT 0").await?;
+    migrate_redfish_config(db).await?;
+    // 장치 그룹
+    add_column_if_not_exists(db, "device", "group_id", "INTEGER").await?;
+
+    // 장치별 서비스 헬스 체크 (TCP 포트 연결, HTTP GET)
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS probe (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            device_id INTEGER NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            kind VARCHAR(10) NOT NULL,
+            port INTEGER,
+            url VARCHAR(2048),
+            expected_status INTEGER,
+            body_contains VARCHAR(255),
+            interval_secs INTEGER NOT NULL,
+            timeout_ms INTEGER NOT NULL,
+            status VARCHAR(10) NOT NULL DEFAULT 'unknown',
+            last_checked datetime,
+            last_latency_ms INTEGER,
+            last_error VARCHAR(255)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+
+    // 장치별 HTTP 동작과 실행 기록
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS device_action (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            device_id INTEGER NOT NULL,
+            name VARCHAR(64) NOT NULL,
+            method VARCHAR(10) NOT NULL,
+            url VARCHAR(2048) NOT NULL,
+            headers TEXT,
+            body TEXT,
+            expected_status INTEGER,
+            timeout_ms INTEGER NOT NULL,
+            UNIQUE(device_id, name)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS action_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            device_id INTEGER NOT NULL,
+            action_name VARCHAR(64) NOT NULL,
+            executed_at datetime NOT NULL,
+            success BOOLEAN NOT NULL,
+            status_code INTEGER,
+            latency_ms INTEGER,
+            error VARCHAR(255)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+
+    // 장치 그룹(자체 순서를 가짐)과 태그
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS device_group (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            order_num INTEGER NOT NULL,
+            wake_stagger_ms INTEGER
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS device_tag (
+            device_id INTEGER NOT NULL,
+            tag VARCHAR(64) NOT NULL,
+            PRIMARY KEY (device_id, tag)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+
+    // 장치 간 웨이크 의존 관계 (device_id를 켜기 전에 depends_on을 먼저 켬)
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS device_dependency (
+            device_id INTEGER NOT NULL,
+            depends_on INTEGER NOT NULL,
+            PRIMARY KEY (device_id, depends_on)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+
+    // 씬(여러 단계의 자동화 흐름)과 실행 기록
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS scene (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name VARCHAR(255) NOT NULL UNIQUE
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS scene_step (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scene_id INTEGER NOT NULL,
+            step_order INTEGER NOT NULL,
+            kind VARCHAR(20) NOT NULL,
+            device_id INTEGER,
+            seconds INTEGER,
+            action_name VARCHAR(64),
+            message TEXT,
+            url VARCHAR(2048)
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS scene_run (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            scene_id INTEGER NOT NULL,
+            status VARCHAR(20) NOT NULL,
+            started_at datetime NOT NULL,
+            finished_at datetime,
+            error TEXT
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+    sqlx::query(
+        r#"CREATE TABLE IF NOT EXISTS scene_run_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            run_id INTEGER NOT NULL,
+            step_order INTEGER NOT NULL,
+            kind VARCHAR(20) NOT NULL,
+            device_id INTEGER,
+            success BOOLEAN NOT NULL,
+            message TEXT,
+            logged_at datetime NOT NULL
+        )"#,
+    )
+        .execute(&db.0)
+        .await?;
+
+    // 서버가 종료되어 끝나지 못한 씬 실행은 실패로 기록 (실행 작업은 재시작 후 이어지지 않음)
+    sqlx::query("UPDATE scene_run SET status = 'failed', error = 'interrupted', finished_at = datetime('now') \
+                 WHERE status = 'running'")
+        .execute(&db.0)
+        .await?;
+
+    // MAC 주소를 저장 형식(AA:BB:CC:DD:EE:FF)으로 통일한 뒤 중복 등록 방지
+    normalize_device_macs(db).await?;
+    let duplicated: Vec<(String,)> = sqlx::query_as("SELECT mac FROM device GROUP BY mac HAVING COUNT(*) > 1")
+        .fetch_all(&db.0)
+        .await?;
+    // 중복된 장치가 있다면 어느 장치를 남길지 알 수 없으므로 제약 없이 실행하지 않고 시작을 중단
+    if !duplicated.is_empty() {
+        let macs: Vec<String> = duplicated.into_iter().map(|(mac,)| mac).collect();
+        return Err(SystemError::APIError(500, 0, format!(
+            "Duplicate device MAC addresses found ({}). Remove the duplicated devices from the database and restart.",
+            macs.join(", "))));
+    }
+    sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_device_mac ON device(mac)")
+        .execute(&db.0)
+        .await?;
+
+    Ok(())
+}
+
+// power_config JSON에 저장하던 Redfish 접속 정보를 bmc_* 항목으로 이동
+// (비밀번호는 같은 키로 암호화되어 있으므로 그대로 복사, off_reset_type 등 나머지 설정은 power_config에 유지)
+async fn migrate_redfish_config(db: &Db) -> Result<(), SystemError> {
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT id, power_config FROM device WHERE power_driver = 'redfish' AND bmc_url IS NULL AND power_config IS NOT NULL")
+        .fetch_all(&db.0)
+        .await?;
+    for (id, power_config) in rows {
+        // 형식이 잘못된 값은 그대로 둠
+        let Ok(mut config) = serde_json::from_str::<Map<String, Value>>(&power_config) else {
+            continue;
+        };
+        let mut take = |key: &str| match config.remove(key) {
+            Some(Value::String(value)) if !value.is_empty() => Some(value),
+            _ => None,
+        };
+        let (url, username, password, system_id) = (take("url"), take("username"), take("password"), take("system_id"));
+        let insecure = config.remove("insecure").and_then(|value| value.as_bool()).unwrap_or(false);
+        let remaining = (!config.is_empty()).then(|| Value::Object(config).to_string());
+        sqlx::query("UPDATE device SET bmc_url = ?, bmc_user = ?, bmc_password = ?, bmc_system_id = ?, bmc_insecure = ?, \
+                     power_config = ? WHERE id = ?")
+            .bind(url)
+            .bind(username)
+            .bind(password)
+            .bind(system_id)
+            .bind(insecure)
+            .bind(remaining)
+            .bind(id)
+            .execute(&db.0)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn normalize_device_macs(db: &Db) -> Result<(), SystemError> {
+    let rows: Vec<(i64, String)> = sqlx::query_as("SELECT id, mac FROM device")
+        .fetch_all(&db.0)
+        .await?;
+    for (id, mac) in rows {
+        // 파싱할 수 없는 값은 그대로 둠
+        if let Ok(normalized) = normalize_mac_address(&mac)
+            && normalized != mac {
+            sqlx::query("UPDATE device SET mac = ? WHERE id = ?")
+                .bind(normalized)
+                .bind(id)
+                .execute(&db.0)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn add_column_if_not_exists(db: &Db, table: &str, column: &str, definition: &str) -> Result<(), SystemError> {
+    let row = sqlx::query("SELECT name FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_optional(&db.0)
+        .await?;
+    if row.is_none() {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(&db.0)
+            .await?;
+    }
+    Ok(())
+}
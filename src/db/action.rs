@@ -0,0 +1,164 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+// 장치별 HTTP 동작 (NAS 재부팅, 가상머신 시작, 스마트 플러그 제어 등)
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct DeviceAction {
+    pub id: i64,
+    pub device_id: i64,
+    // 장치 내에서 유일한 이름 (API 경로에 사용)
+    pub name: String,
+    // GET, POST, PUT, PATCH, DELETE
+    pub method: String,
+    // 요청 URL ({ip}, {mac}, {name}은 장치 값으로 치환)
+    pub url: String,
+    // 요청 헤더 JSON 객체 (토큰 등이 포함될 수 있으므로 module::crypto로 암호화된 값)
+    pub headers: Option<String>,
+    // 요청 본문 템플릿 (URL과 같은 치환 규칙)
+    pub body: Option<String>,
+    // 기대하는 상태 코드 (미지정시 2xx)
+    pub expected_status: Option<i64>,
+    pub timeout_ms: i64,
+}
+
+// HTTP 동작 실행 기록
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct ActionLog {
+    pub id: i64,
+    pub device_id: i64,
+    pub action_name: String,
+    pub executed_at: String,
+    pub success: bool,
+    pub status_code: Option<i64>,
+    pub latency_ms: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[async_trait::async_trait]
+pub trait DeviceActionOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceAction>, sqlx::Error>;
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceAction>, sqlx::Error>;
+    async fn get_by_name(pool: &SqlitePool, device_id: i64, name: &str) -> Result<Option<DeviceAction>, sqlx::Error>;
+    async fn save_for_device(conn: &mut SqliteConnection, device_id: i64, actions: &[DeviceAction]) -> Result<(), sqlx::Error>;
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl DeviceActionOperations for DeviceAction {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceAction>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceAction>("SELECT * FROM device_action ORDER BY device_id, id")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceAction>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceAction>("SELECT * FROM device_action WHERE device_id = ? ORDER BY id")
+            .bind(device_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_name(pool: &SqlitePool, device_id: i64, name: &str) -> Result<Option<DeviceAction>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceAction>("SELECT * FROM device_action WHERE device_id = ? AND name = ?")
+            .bind(device_id)
+            .bind(name)
+            .fetch_optional(pool)
+            .await
+    }
+
+    // 장치의 동작 목록을 요청 목록으로 교체 (기존 항목은 id를 유지)
+    async fn save_for_device(conn: &mut SqliteConnection, device_id: i64, actions: &[DeviceAction]) -> Result<(), sqlx::Error> {
+        let existing = sqlx::query_as::<_, DeviceAction>("SELECT * FROM device_action WHERE device_id = ?")
+            .bind(device_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let mut tx = conn.begin().await?;
+
+        for old in existing.iter().filter(|old| !actions.iter().any(|a| a.id == old.id)) {
+            sqlx::query("DELETE FROM device_action WHERE id = ?")
+                .bind(old.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for action in actions {
+            if existing.iter().any(|old| old.id == action.id) {
+                sqlx::query("UPDATE device_action SET name = ?, method = ?, url = ?, headers = ?, body = ?, \
+                             expected_status = ?, timeout_ms = ? WHERE id = ? AND device_id = ?")
+                    .bind(&action.name)
+                    .bind(&action.method)
+                    .bind(&action.url)
+                    .bind(&action.headers)
+                    .bind(&action.body)
+                    .bind(action.expected_status)
+                    .bind(action.timeout_ms)
+                    .bind(action.id)
+                    .bind(device_id)
+                    .execute(&mut *tx)
+                    .await?;
+            } else {
+                sqlx::query("INSERT INTO device_action (device_id, name, method, url, headers, body, expected_status, timeout_ms) \
+                             VALUES (?, ?, ?, ?, ?, ?, ?, ?)")
+                    .bind(device_id)
+                    .bind(&action.name)
+                    .bind(&action.method)
+                    .bind(&action.url)
+                    .bind(&action.headers)
+                    .bind(&action.body)
+                    .bind(action.expected_status)
+                    .bind(action.timeout_ms)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await
+    }
+
+    // 장치의 동작과 실행 기록(action_log)을 함께 삭제
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM device_action WHERE device_id = ?")
+            .bind(device_id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("DELETE FROM action_log WHERE device_id = ?")
+            .bind(device_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+pub trait ActionLogOperations {
+    async fn insert(pool: &SqlitePool, log: &ActionLog) -> Result<(), sqlx::Error>;
+    async fn get_by_device(pool: &SqlitePool, device_id: i64, limit: i64) -> Result<Vec<ActionLog>, sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl ActionLogOperations for ActionLog {
+    async fn insert(pool: &SqlitePool, log: &ActionLog) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO action_log (device_id, action_name, executed_at, success, status_code, latency_ms, error) \
+                     VALUES (?, ?, datetime('now'), ?, ?, ?, ?)")
+            .bind(log.device_id)
+            .bind(&log.action_name)
+            .bind(log.success)
+            .bind(log.status_code)
+            .bind(log.latency_ms)
+            .bind(&log.error)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // 최근 실행 기록부터 조회
+    async fn get_by_device(pool: &SqlitePool, device_id: i64, limit: i64) -> Result<Vec<ActionLog>, sqlx::Error> {
+        sqlx::query_as::<_, ActionLog>("SELECT * FROM action_log WHERE device_id = ? ORDER BY id DESC LIMIT ?")
+            .bind(device_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+    }
+}
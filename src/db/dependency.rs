@@ -0,0 +1,62 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+// 장치가 켜지기 전에 먼저 켜져 있어야 하는 장치 (NAS, 도메인 컨트롤러 등)
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct DeviceDependency {
+    pub device_id: i64,
+    pub depends_on: i64,
+}
+
+#[async_trait::async_trait]
+pub trait DependencyOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceDependency>, sqlx::Error>;
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceDependency>, sqlx::Error>;
+    async fn set_for_device(conn: &mut SqliteConnection, device_id: i64, depends_on: &[i64]) -> Result<(), sqlx::Error>;
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl DependencyOperations for DeviceDependency {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceDependency>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceDependency>("SELECT device_id, depends_on FROM device_dependency ORDER BY device_id, depends_on")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceDependency>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceDependency>("SELECT device_id, depends_on FROM device_dependency WHERE device_id = ? ORDER BY depends_on")
+            .bind(device_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    // 장치의 의존 대상 목록을 교체
+    async fn set_for_device(conn: &mut SqliteConnection, device_id: i64, depends_on: &[i64]) -> Result<(), sqlx::Error> {
+        let mut tx = conn.begin().await?;
+        sqlx::query("DELETE FROM device_dependency WHERE device_id = ?")
+            .bind(device_id)
+            .execute(&mut *tx)
+            .await?;
+        for dependency in depends_on {
+            sqlx::query("INSERT OR IGNORE INTO device_dependency (device_id, depends_on) VALUES (?, ?)")
+                .bind(device_id)
+                .bind(dependency)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+
+    // 장치가 의존하는 항목과 장치에 의존하는 항목을 모두 삭제
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM device_dependency WHERE device_id = ? OR depends_on = ?")
+            .bind(device_id)
+            .bind(device_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
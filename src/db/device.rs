@@ -1,27 +1,100 @@
 use rocket::serde::{Deserialize, Serialize};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
-#[derive(Serialize, Deserialize, Clone, sqlx::FromRow)]
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
 pub struct Device {
     pub id: i64,
     pub name: String,
     pub mac: String,
     pub ip: String,
     pub order_num: i64,
+    // 매직패킷 전송 대상 브로드캐스트 주소 (미지정시 ip/netmask로 계산한 서브넷 브로드캐스트)
+    pub broadcast: Option<String>,
+    pub netmask: Option<String>,
+    pub port: i64,
+    // 송신 인터페이스 (인터페이스 이름 또는 로컬 IP)
+    pub interface: Option<String>,
+    // SecureOn 비밀번호 (module::crypto로 암호화된 값)
+    pub secureon: Option<String>,
+    // 전송 방식 (udp, ethernet, both)
+    pub transport: String,
+    pub vlan_id: Option<i64>,
+    // 전송 정책 (미지정시 WolConfig 기본값)
+    pub burst_count: Option<i64>,
+    pub burst_interval_ms: Option<i64>,
+    // 콤마로 구분된 전송 대상 목록 (broadcast, subnet, unicast)
+    pub wake_targets: Option<String>,
+    // 웨이크 후 응답 확인에 사용할 TCP 포트 (ICMP 응답이 없는 경우)
+    pub verify_port: Option<i64>,
+    // SSH 원격 종료 설정 (비밀번호/개인키는 module::crypto로 암호화된 값)
+    pub ssh_user: Option<String>,
+    pub ssh_port: Option<i64>,
+    pub ssh_password: Option<String>,
+    pub ssh_private_key: Option<String>,
+    // 실행할 명령 (미지정시 SshConfig 기본값)
+    pub ssh_command: Option<String>,
+    // 처음 접속시 저장한 서버 호스트 키 지문 (이후 접속시 일치해야 함)
+    pub ssh_host_key: Option<String>,
+    // Sleep-on-LAN 에이전트가 설치되어 있어 역방향 매직패킷으로 절전시킬 수 있는지
+    pub sleep_on_lan: bool,
+    // 전원 제어 드라이버 (wol, redfish, ipmi, smart_plug)
+    pub power_driver: String,
+    // 드라이버별 설정 JSON (password 항목은 module::crypto로 암호화된 값)
+    pub power_config: Option<String>,
+    // Redfish BMC 접속 정보 (bmc_password는 module::crypto로 암호화된 값)
+    pub bmc_url: Option<String>,
+    pub bmc_user: Option<String>,
+    pub bmc_password: Option<String>,
+    // 제어할 시스템 ID (미지정시 BMC의 첫 번째 시스템)
+    pub bmc_system_id: Option<String>,
+    // BMC의 자체 서명 인증서 허용
+    pub bmc_insecure: bool,
+    // 소속 그룹 (미지정시 그룹 없음)
+    pub group_id: Option<i64>,
+    // 상태 모니터링 결과 (update_status로만 갱신됨)
+    pub status: String,
+    pub last_seen: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MoveDirection { Up, Down }
 
+impl MoveDirection {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "up" => Some(MoveDirection::Up),
+            "down" => Some(MoveDirection::Down),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceStatus { Unknown, Online, Offline }
+
+impl DeviceStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeviceStatus::Unknown => "unknown",
+            DeviceStatus::Online => "online",
+            DeviceStatus::Offline => "offline",
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait DeviceOperations {
     async fn get_all(pool: &SqlitePool) -> Result<Vec<Device>, sqlx::Error>;
     async fn get(pool: &SqlitePool, id: i64) -> Result<Option<Device>, sqlx::Error>;
+    async fn get_by_group(pool: &SqlitePool, group_id: i64) -> Result<Vec<Device>, sqlx::Error>;
     async fn get_max_order_num(pool: &SqlitePool) -> Result<i64, sqlx::Error>;
-    async fn insert(pool: &SqlitePool, device: &Device) -> Result<Device, sqlx::Error>;
-    async fn update(&self, pool: &SqlitePool) -> Result<(), sqlx::Error>;
-    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error>;
+    // 관련 항목과 함께 저장할 수 있도록 트랜잭션의 연결을 받음
+    async fn insert(conn: &mut SqliteConnection, device: &Device) -> Result<Device, sqlx::Error>;
+    async fn update(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error>;
+    async fn delete(conn: &mut SqliteConnection, id: i64) -> Result<(), sqlx::Error>;
     async fn move_order(pool: &SqlitePool, id: i64, direction: MoveDirection) -> Result<bool, sqlx::Error>;
+    async fn update_status(pool: &SqlitePool, id: i64, status: DeviceStatus) -> Result<(), sqlx::Error>;
+    async fn update_ssh_host_key(pool: &SqlitePool, id: i64, host_key: &str) -> Result<(), sqlx::Error>;
 }
 
 #[async_trait::async_trait]
@@ -39,6 +112,13 @@ impl DeviceOperations for Device {
             .await
     }
 
+    async fn get_by_group(pool: &SqlitePool, group_id: i64) -> Result<Vec<Device>, sqlx::Error> {
+        sqlx::query_as::<_, Device>("SELECT * FROM device WHERE group_id = ? ORDER BY order_num")
+            .bind(group_id)
+            .fetch_all(pool)
+            .await
+    }
+
     async fn get_max_order_num(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
         let result: Option<(Option<i64>,)> = sqlx::query_as("SELECT MAX(order_num) FROM device")
             .fetch_optional(pool)
@@ -47,43 +127,102 @@ impl DeviceOperations for Device {
         Ok(result.and_then(|r| r.0).unwrap_or(0))
     }
 
-    async fn insert(pool: &SqlitePool, device: &Device) -> Result<Device, sqlx::Error> {
-        let result = sqlx::query("INSERT INTO device (name, mac, ip, order_num) VALUES (?, ?, ?, ?)")
+    async fn insert(conn: &mut SqliteConnection, device: &Device) -> Result<Device, sqlx::Error> {
+        let result = sqlx::query("INSERT INTO device (name, mac, ip, order_num, broadcast, netmask, port, interface, secureon, \
+            transport, vlan_id, burst_count, burst_interval_ms, wake_targets, verify_port, \
+            ssh_user, ssh_port, ssh_password, ssh_private_key, ssh_command, ssh_host_key, sleep_on_lan, \
+            power_driver, power_config, bmc_url, bmc_user, bmc_password, bmc_system_id, bmc_insecure, group_id) \
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
             .bind(&device.name)
             .bind(&device.mac)
             .bind(&device.ip)           
             .bind(device.order_num)
-            .execute(pool)
+            .bind(&device.broadcast)
+            .bind(&device.netmask)
+            .bind(device.port)
+            .bind(&device.interface)
+            .bind(&device.secureon)
+            .bind(&device.transport)
+            .bind(device.vlan_id)
+            .bind(device.burst_count)
+            .bind(device.burst_interval_ms)
+            .bind(&device.wake_targets)
+            .bind(device.verify_port)
+            .bind(&device.ssh_user)
+            .bind(device.ssh_port)
+            .bind(&device.ssh_password)
+            .bind(&device.ssh_private_key)
+            .bind(&device.ssh_command)
+            .bind(&device.ssh_host_key)
+            .bind(device.sleep_on_lan)
+            .bind(&device.power_driver)
+            .bind(&device.power_config)
+            .bind(&device.bmc_url)
+            .bind(&device.bmc_user)
+            .bind(&device.bmc_password)
+            .bind(&device.bmc_system_id)
+            .bind(device.bmc_insecure)
+            .bind(device.group_id)
+            .execute(conn)
             .await?;
 
         let id = result.last_insert_rowid();
 
         Ok(Device {
             id,
-            name: device.name.clone(),
-            mac: device.mac.clone(),
-            ip: device.ip.clone(),
-            order_num: device.order_num,
+            ..device.clone()
         })
     }
 
-    async fn update(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE device SET name = ?, mac = ?, ip = ?, order_num = ? WHERE id = ?")
+    async fn update(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE device SET name = ?, mac = ?, ip = ?, order_num = ?, \
+            broadcast = ?, netmask = ?, port = ?, interface = ?, secureon = ?, transport = ?, vlan_id = ?, \
+            burst_count = ?, burst_interval_ms = ?, wake_targets = ?, verify_port = ?, \
+            ssh_user = ?, ssh_port = ?, ssh_password = ?, ssh_private_key = ?, ssh_command = ?, ssh_host_key = ?, \
+            sleep_on_lan = ?, power_driver = ?, power_config = ?, \
+            bmc_url = ?, bmc_user = ?, bmc_password = ?, bmc_system_id = ?, bmc_insecure = ?, \
+            group_id = ? WHERE id = ?")
             .bind(&self.name)
             .bind(&self.mac)
             .bind(&self.ip)           
             .bind(self.order_num)
+            .bind(&self.broadcast)
+            .bind(&self.netmask)
+            .bind(self.port)
+            .bind(&self.interface)
+            .bind(&self.secureon)
+            .bind(&self.transport)
+            .bind(self.vlan_id)
+            .bind(self.burst_count)
+            .bind(self.burst_interval_ms)
+            .bind(&self.wake_targets)
+            .bind(self.verify_port)
+            .bind(&self.ssh_user)
+            .bind(self.ssh_port)
+            .bind(&self.ssh_password)
+            .bind(&self.ssh_private_key)
+            .bind(&self.ssh_command)
+            .bind(&self.ssh_host_key)
+            .bind(self.sleep_on_lan)
+            .bind(&self.power_driver)
+            .bind(&self.power_config)
+            .bind(&self.bmc_url)
+            .bind(&self.bmc_user)
+            .bind(&self.bmc_password)
+            .bind(&self.bmc_system_id)
+            .bind(self.bmc_insecure)
+            .bind(self.group_id)
             .bind(self.id)
-            .execute(pool)
+            .execute(conn)
             .await?;
 
         Ok(())
     }
 
-    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    async fn delete(conn: &mut SqliteConnection, id: i64) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM device WHERE id = ?")
             .bind(id)
-            .execute(pool)
+            .execute(conn)
             .await?;
 
         Ok(())
@@ -129,4 +268,30 @@ impl DeviceOperations for Device {
             Ok(false)
         }
     }
+
+    async fn update_status(pool: &SqlitePool, id: i64, status: DeviceStatus) -> Result<(), sqlx::Error> {
+        // 응답이 있었던 경우에만 마지막 확인 시각 갱신
+        let query = if status == DeviceStatus::Online {
+            "UPDATE device SET status = ?, last_seen = datetime('now') WHERE id = ?"
+        } else {
+            "UPDATE device SET status = ? WHERE id = ?"
+        };
+        sqlx::query(query)
+            .bind(status.as_str())
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_ssh_host_key(pool: &SqlitePool, id: i64, host_key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE device SET ssh_host_key = ? WHERE id = ?")
+            .bind(host_key)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
 }
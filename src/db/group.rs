@@ -0,0 +1,123 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use crate::db::device::MoveDirection;
+
+// 장치 그룹 (실험실, 랙 등)
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct DeviceGroup {
+    pub id: i64,
+    pub name: String,
+    pub order_num: i64,
+    // 그룹 웨이크시 장치 사이의 대기 시간 (미지정시 WolConfig 기본값)
+    pub wake_stagger_ms: Option<i64>,
+}
+
+#[async_trait::async_trait]
+pub trait GroupOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceGroup>, sqlx::Error>;
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<DeviceGroup>, sqlx::Error>;
+    async fn get_max_order_num(pool: &SqlitePool) -> Result<i64, sqlx::Error>;
+    async fn insert(pool: &SqlitePool, group: &DeviceGroup) -> Result<DeviceGroup, sqlx::Error>;
+    async fn update(&self, pool: &SqlitePool) -> Result<(), sqlx::Error>;
+    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error>;
+    async fn move_order(pool: &SqlitePool, id: i64, direction: MoveDirection) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl GroupOperations for DeviceGroup {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceGroup>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceGroup>("SELECT * FROM device_group ORDER BY order_num")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<DeviceGroup>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceGroup>("SELECT * FROM device_group WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    async fn get_max_order_num(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        let result: Option<(Option<i64>,)> = sqlx::query_as("SELECT MAX(order_num) FROM device_group")
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(result.and_then(|r| r.0).unwrap_or(0))
+    }
+
+    async fn insert(pool: &SqlitePool, group: &DeviceGroup) -> Result<DeviceGroup, sqlx::Error> {
+        let result = sqlx::query("INSERT INTO device_group (name, order_num, wake_stagger_ms) VALUES (?, ?, ?)")
+            .bind(&group.name)
+            .bind(group.order_num)
+            .bind(group.wake_stagger_ms)
+            .execute(pool)
+            .await?;
+
+        Ok(DeviceGroup {
+            id: result.last_insert_rowid(),
+            ..group.clone()
+        })
+    }
+
+    async fn update(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE device_group SET name = ?, wake_stagger_ms = ? WHERE id = ?")
+            .bind(&self.name)
+            .bind(self.wake_stagger_ms)
+            .bind(self.id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // 그룹에 속한 장치는 그룹 미지정으로 변경
+    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("UPDATE device SET group_id = NULL WHERE group_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM device_group WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await
+    }
+
+    async fn move_order(pool: &SqlitePool, id: i64, direction: MoveDirection) -> Result<bool, sqlx::Error> {
+        let current = match Self::get(pool, id).await? {
+            Some(group) => group,
+            None => return Ok(false),
+        };
+
+        let query = match direction {
+            MoveDirection::Up => "SELECT * FROM device_group WHERE order_num < ? ORDER BY order_num DESC LIMIT 1",
+            MoveDirection::Down => "SELECT * FROM device_group WHERE order_num > ? ORDER BY order_num ASC LIMIT 1",
+        };
+        let adjacent: Option<DeviceGroup> = sqlx::query_as::<_, DeviceGroup>(query)
+            .bind(current.order_num)
+            .fetch_optional(pool)
+            .await?;
+
+        let Some(adjacent) = adjacent else {
+            return Ok(false);
+        };
+        // order_num 교환
+        let mut tx = pool.begin().await?;
+        sqlx::query("UPDATE device_group SET order_num = ? WHERE id = ?")
+            .bind(adjacent.order_num)
+            .bind(current.id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE device_group SET order_num = ? WHERE id = ?")
+            .bind(current.order_num)
+            .bind(adjacent.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,134 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+// 장치별 서비스 헬스 체크 설정 및 마지막 결과
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct Probe {
+    pub id: i64,
+    pub device_id: i64,
+    pub name: String,
+    // tcp 또는 http
+    pub kind: String,
+    // tcp: 연결할 포트
+    pub port: Option<i64>,
+    // http: 요청 URL ({ip}는 장치 IP로 치환)
+    pub url: Option<String>,
+    // http: 기대하는 상태 코드 (미지정시 2xx)
+    pub expected_status: Option<i64>,
+    // http: 응답 본문에 포함되어야 하는 문자열
+    pub body_contains: Option<String>,
+    pub interval_secs: i64,
+    pub timeout_ms: i64,
+    // 마지막 확인 결과 (unknown, up, down)
+    pub status: String,
+    pub last_checked: Option<String>,
+    pub last_latency_ms: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[async_trait::async_trait]
+pub trait ProbeOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<Probe>, sqlx::Error>;
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<Probe>, sqlx::Error>;
+    async fn get_due(pool: &SqlitePool) -> Result<Vec<Probe>, sqlx::Error>;
+    async fn save_for_device(conn: &mut SqliteConnection, device_id: i64, probes: &[Probe]) -> Result<(), sqlx::Error>;
+    async fn update_result(pool: &SqlitePool, id: i64, up: bool, latency_ms: Option<i64>, error: Option<&str>) -> Result<(), sqlx::Error>;
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl ProbeOperations for Probe {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<Probe>, sqlx::Error> {
+        sqlx::query_as::<_, Probe>("SELECT * FROM probe ORDER BY device_id, id")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<Probe>, sqlx::Error> {
+        sqlx::query_as::<_, Probe>("SELECT * FROM probe WHERE device_id = ? ORDER BY id")
+            .bind(device_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    // 확인 주기가 지난 항목 조회
+    async fn get_due(pool: &SqlitePool) -> Result<Vec<Probe>, sqlx::Error> {
+        sqlx::query_as::<_, Probe>("SELECT * FROM probe WHERE last_checked IS NULL \
+            OR last_checked <= datetime('now', '-' || interval_secs || ' seconds')")
+            .fetch_all(pool)
+            .await
+    }
+
+    // 장치의 헬스 체크 목록을 요청 목록으로 교체
+    // 기존 항목(id 일치)은 설정만 갱신하여 마지막 결과를 유지
+    async fn save_for_device(conn: &mut SqliteConnection, device_id: i64, probes: &[Probe]) -> Result<(), sqlx::Error> {
+        let existing = sqlx::query_as::<_, Probe>("SELECT * FROM probe WHERE device_id = ?")
+            .bind(device_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let mut tx = conn.begin().await?;
+
+        for old in existing.iter().filter(|old| !probes.iter().any(|p| p.id == old.id)) {
+            sqlx::query("DELETE FROM probe WHERE id = ?")
+                .bind(old.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for probe in probes {
+            if existing.iter().any(|old| old.id == probe.id) {
+                sqlx::query("UPDATE probe SET name = ?, kind = ?, port = ?, url = ?, expected_status = ?, body_contains = ?, \
+                             interval_secs = ?, timeout_ms = ? WHERE id = ? AND device_id = ?")
+                    .bind(&probe.name)
+                    .bind(&probe.kind)
+                    .bind(probe.port)
+                    .bind(&probe.url)
+                    .bind(probe.expected_status)
+                    .bind(&probe.body_contains)
+                    .bind(probe.interval_secs)
+                    .bind(probe.timeout_ms)
+                    .bind(probe.id)
+                    .bind(device_id)
+                    .execute(&mut *tx)
+                    .await?;
+            } else {
+                sqlx::query("INSERT INTO probe (device_id, name, kind, port, url, expected_status, body_contains, interval_secs, timeout_ms) \
+                             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                    .bind(device_id)
+                    .bind(&probe.name)
+                    .bind(&probe.kind)
+                    .bind(probe.port)
+                    .bind(&probe.url)
+                    .bind(probe.expected_status)
+                    .bind(&probe.body_contains)
+                    .bind(probe.interval_secs)
+                    .bind(probe.timeout_ms)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        tx.commit().await
+    }
+
+    async fn update_result(pool: &SqlitePool, id: i64, up: bool, latency_ms: Option<i64>, error: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE probe SET status = ?, last_checked = datetime('now'), last_latency_ms = ?, last_error = ? WHERE id = ?")
+            .bind(if up { "up" } else { "down" })
+            .bind(latency_ms)
+            .bind(error)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM probe WHERE device_id = ?")
+            .bind(device_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+// 여러 단계를 순서대로 실행하는 자동화 흐름 (예: NAS 켜기 -> 응답 대기 -> 백업 시작 -> 알림)
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct Scene {
+    pub id: i64,
+    pub name: String,
+}
+
+// 씬의 단계 (kind에 따라 사용하는 항목이 다름)
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct SceneStep {
+    pub id: i64,
+    pub scene_id: i64,
+    // 0부터 시작하는 실행 순서
+    pub step_order: i64,
+    // wake, wait, wait_online, run_action, shutdown, notify
+    pub kind: String,
+    pub device_id: Option<i64>,
+    // wait: 대기 시간, wait_online: 최대 대기 시간
+    pub seconds: Option<i64>,
+    // run_action: 장치의 HTTP 동작 이름
+    pub action_name: Option<String>,
+    // notify: 알림 내용
+    pub message: Option<String>,
+    // notify: 알림을 POST로 전달할 웹훅 주소 (미지정시 이벤트로만 전달)
+    pub url: Option<String>,
+}
+
+// 씬 실행 기록
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct SceneRun {
+    pub id: i64,
+    pub scene_id: i64,
+    // running, succeeded, failed
+    pub status: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+}
+
+// 씬 실행의 단계별 기록
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct SceneRunLog {
+    pub id: i64,
+    pub run_id: i64,
+    pub step_order: i64,
+    pub kind: String,
+    pub device_id: Option<i64>,
+    pub success: bool,
+    pub message: Option<String>,
+    pub logged_at: String,
+}
+
+#[async_trait::async_trait]
+pub trait SceneOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<Scene>, sqlx::Error>;
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<Scene>, sqlx::Error>;
+    // 단계와 함께 저장할 수 있도록 트랜잭션의 연결을 받음
+    async fn insert(conn: &mut SqliteConnection, scene: &Scene) -> Result<Scene, sqlx::Error>;
+    async fn update(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error>;
+    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl SceneOperations for Scene {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<Scene>, sqlx::Error> {
+        sqlx::query_as::<_, Scene>("SELECT * FROM scene ORDER BY id")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<Scene>, sqlx::Error> {
+        sqlx::query_as::<_, Scene>("SELECT * FROM scene WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    async fn insert(conn: &mut SqliteConnection, scene: &Scene) -> Result<Scene, sqlx::Error> {
+        let result = sqlx::query("INSERT INTO scene (name) VALUES (?)")
+            .bind(&scene.name)
+            .execute(conn)
+            .await?;
+
+        Ok(Scene {
+            id: result.last_insert_rowid(),
+            ..scene.clone()
+        })
+    }
+
+    async fn update(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE scene SET name = ? WHERE id = ?")
+            .bind(&self.name)
+            .bind(self.id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    // 단계와 실행 기록도 함께 삭제
+    async fn delete(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM scene_run_log WHERE run_id IN (SELECT id FROM scene_run WHERE scene_id = ?)")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM scene_run WHERE scene_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM scene_step WHERE scene_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM scene WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await
+    }
+}
+
+#[async_trait::async_trait]
+pub trait SceneStepOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<SceneStep>, sqlx::Error>;
+    async fn get_by_scene(pool: &SqlitePool, scene_id: i64) -> Result<Vec<SceneStep>, sqlx::Error>;
+    async fn save_for_scene(conn: &mut SqliteConnection, scene_id: i64, steps: &[SceneStep]) -> Result<(), sqlx::Error>;
+    async fn get_scene_names_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<Vec<String>, sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl SceneStepOperations for SceneStep {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<SceneStep>, sqlx::Error> {
+        sqlx::query_as::<_, SceneStep>("SELECT * FROM scene_step ORDER BY scene_id, step_order")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_scene(pool: &SqlitePool, scene_id: i64) -> Result<Vec<SceneStep>, sqlx::Error> {
+        sqlx::query_as::<_, SceneStep>("SELECT * FROM scene_step WHERE scene_id = ? ORDER BY step_order")
+            .bind(scene_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    // 씬의 단계 목록을 교체 (목록 순서대로 step_order 부여)
+    async fn save_for_scene(conn: &mut SqliteConnection, scene_id: i64, steps: &[SceneStep]) -> Result<(), sqlx::Error> {
+        let mut tx = conn.begin().await?;
+        sqlx::query("DELETE FROM scene_step WHERE scene_id = ?")
+            .bind(scene_id)
+            .execute(&mut *tx)
+            .await?;
+        for (order, step) in steps.iter().enumerate() {
+            sqlx::query("INSERT INTO scene_step (scene_id, step_order, kind, device_id, seconds, action_name, message, url) \
+                         VALUES (?, ?, ?, ?, ?, ?, ?, ?)")
+                .bind(scene_id)
+                .bind(order as i64)
+                .bind(&step.kind)
+                .bind(step.device_id)
+                .bind(step.seconds)
+                .bind(&step.action_name)
+                .bind(&step.message)
+                .bind(&step.url)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+
+    // 장치를 단계에서 사용하는 씬 이름 목록 (장치 삭제 전 확인용)
+    async fn get_scene_names_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, String>("SELECT DISTINCT scene.name FROM scene \
+            JOIN scene_step ON scene_step.scene_id = scene.id WHERE scene_step.device_id = ? ORDER BY scene.name")
+            .bind(device_id)
+            .fetch_all(conn)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+pub trait SceneRunOperations {
+    async fn insert(pool: &SqlitePool, scene_id: i64) -> Result<SceneRun, sqlx::Error>;
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<SceneRun>, sqlx::Error>;
+    async fn get_by_scene(pool: &SqlitePool, scene_id: i64, limit: i64) -> Result<Vec<SceneRun>, sqlx::Error>;
+    async fn finish(pool: &SqlitePool, id: i64, status: &str, error: Option<&str>) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl SceneRunOperations for SceneRun {
+    // running 상태로 실행 기록 생성
+    async fn insert(pool: &SqlitePool, scene_id: i64) -> Result<SceneRun, sqlx::Error> {
+        let result = sqlx::query("INSERT INTO scene_run (scene_id, status, started_at) VALUES (?, 'running', datetime('now'))")
+            .bind(scene_id)
+            .execute(pool)
+            .await?;
+
+        Self::get(pool, result.last_insert_rowid()).await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn get(pool: &SqlitePool, id: i64) -> Result<Option<SceneRun>, sqlx::Error> {
+        sqlx::query_as::<_, SceneRun>("SELECT * FROM scene_run WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    // 최근 실행부터 조회
+    async fn get_by_scene(pool: &SqlitePool, scene_id: i64, limit: i64) -> Result<Vec<SceneRun>, sqlx::Error> {
+        sqlx::query_as::<_, SceneRun>("SELECT * FROM scene_run WHERE scene_id = ? ORDER BY id DESC LIMIT ?")
+            .bind(scene_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn finish(pool: &SqlitePool, id: i64, status: &str, error: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE scene_run SET status = ?, finished_at = datetime('now'), error = ? WHERE id = ?")
+            .bind(status)
+            .bind(error)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+pub trait SceneRunLogOperations {
+    async fn insert(pool: &SqlitePool, log: &SceneRunLog) -> Result<(), sqlx::Error>;
+    async fn get_by_run(pool: &SqlitePool, run_id: i64) -> Result<Vec<SceneRunLog>, sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl SceneRunLogOperations for SceneRunLog {
+    async fn insert(pool: &SqlitePool, log: &SceneRunLog) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO scene_run_log (run_id, step_order, kind, device_id, success, message, logged_at) \
+                     VALUES (?, ?, ?, ?, ?, ?, datetime('now'))")
+            .bind(log.run_id)
+            .bind(log.step_order)
+            .bind(&log.kind)
+            .bind(log.device_id)
+            .bind(log.success)
+            .bind(&log.message)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_by_run(pool: &SqlitePool, run_id: i64) -> Result<Vec<SceneRunLog>, sqlx::Error> {
+        sqlx::query_as::<_, SceneRunLog>("SELECT * FROM scene_run_log WHERE run_id = ? ORDER BY id")
+            .bind(run_id)
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -0,0 +1,60 @@
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+// 장치에 붙이는 자유 형식 태그
+#[derive(Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct DeviceTag {
+    pub device_id: i64,
+    pub tag: String,
+}
+
+#[async_trait::async_trait]
+pub trait TagOperations {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceTag>, sqlx::Error>;
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceTag>, sqlx::Error>;
+    async fn set_for_device(conn: &mut SqliteConnection, device_id: i64, tags: &[String]) -> Result<(), sqlx::Error>;
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait::async_trait]
+impl TagOperations for DeviceTag {
+    async fn get_all(pool: &SqlitePool) -> Result<Vec<DeviceTag>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceTag>("SELECT device_id, tag FROM device_tag ORDER BY device_id, tag")
+            .fetch_all(pool)
+            .await
+    }
+
+    async fn get_by_device(pool: &SqlitePool, device_id: i64) -> Result<Vec<DeviceTag>, sqlx::Error> {
+        sqlx::query_as::<_, DeviceTag>("SELECT device_id, tag FROM device_tag WHERE device_id = ? ORDER BY tag")
+            .bind(device_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    // 장치의 태그 목록을 교체
+    async fn set_for_device(conn: &mut SqliteConnection, device_id: i64, tags: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = conn.begin().await?;
+        sqlx::query("DELETE FROM device_tag WHERE device_id = ?")
+            .bind(device_id)
+            .execute(&mut *tx)
+            .await?;
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO device_tag (device_id, tag) VALUES (?, ?)")
+                .bind(device_id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+
+    async fn delete_by_device(conn: &mut SqliteConnection, device_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM device_tag WHERE device_id = ?")
+            .bind(device_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
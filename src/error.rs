@@ -31,6 +31,17 @@ pub enum SystemError {
     Argon2Error(password_hash::Error),
 }
 
+impl SystemError {
+    // 응답/리포트에 표시할 에러 메시지
+    pub fn message(&self) -> String {
+        match self {
+            SystemError::APIError(_, _, message) => message.clone(),
+            SystemError::Sql(e) => format!("Database error: {}", e),
+            _ => self.to_string(),
+        }
+    }
+}
+
 impl From<password_hash::Error> for SystemError {
     fn from(err: password_hash::Error) -> Self {
         SystemError::Argon2Error(err)
@@ -8,6 +8,7 @@ mod config;
 #[macro_use] extern crate rocket;
 
 use std::thread;
+use std::sync::Arc;
 use std::io::Write;
 use std::fs;
 use rocket::fairing::AdHoc;
@@ -19,14 +20,25 @@ use rocket::tokio::runtime::Runtime;
 use rocket_cors::{AllowedOrigins, CorsOptions};
 use rocket_db_pools::Database;
 use tray_item::{IconSource, TrayItem};
-use crate::config::{CorsConfig, JwtConfig};
-use crate::db::{create_tables, is_exist_tables, Db};
+use crate::config::{CorsConfig, CryptoConfig, DiscoveryConfig, IpmiConfig, JwtConfig, MonitorConfig, SshConfig, WolConfig};
+use crate::db::{create_tables, is_exist_tables, migrate_tables, Db};
 use crate::module::jwt::generate_random_secret;
+use crate::module::transport::create_transport;
+use crate::module::events::EventBus;
+use crate::module::monitor::start_monitor;
+use crate::module::power::PowerResources;
+use crate::module::wake_job::WakeJobs;
 
-fn update_secret_in_config(secret: &str) -> std::io::Result<()> {
+fn update_secret_in_config(section: &str, name: &str, secret: &str) -> std::io::Result<()> {
     let config_path = "Rocket.toml";
     let content = fs::read_to_string(config_path)?;
-    let updated_content = content.replace(r#"secret = "generate""#, &format!(r#"secret = "{}""#, secret));
+    let placeholder = format!(r#"{} = "generate""#, name);
+    let updated_content = if content.contains(&placeholder) {
+        content.replace(&placeholder, &format!(r#"{} = "{}""#, name, secret))
+    } else {
+        // 설정 섹션 자체가 없는 경우 새로 추가
+        format!("{}\n\n[default.{}]\n{} = \"{}\"\n", content.trim_end(), section, name, secret)
+    };
     fs::write(config_path, updated_content)?;
     Ok(())
 }
@@ -38,15 +50,44 @@ fn build_rocket() -> rocket::Rocket<rocket::Build> {
     let cors_config = rocket::Config::figment()
         .extract_inner::<CorsConfig>("cors")
         .unwrap_or_default();
+    let mut crypto_config = rocket::Config::figment()
+        .extract_inner::<CryptoConfig>("crypto")
+        .unwrap_or_default();
+    let wol_config = rocket::Config::figment()
+        .extract_inner::<WolConfig>("wol")
+        .unwrap_or_default();
+    let monitor_config = rocket::Config::figment()
+        .extract_inner::<MonitorConfig>("monitor")
+        .unwrap_or_default();
+    let discovery_config = rocket::Config::figment()
+        .extract_inner::<DiscoveryConfig>("discovery")
+        .unwrap_or_default();
+    let ssh_config = rocket::Config::figment()
+        .extract_inner::<SshConfig>("ssh")
+        .unwrap_or_default();
+    let ipmi_config = rocket::Config::figment()
+        .extract_inner::<IpmiConfig>("ipmi")
+        .unwrap_or_default();
 
     if jwt_config.secret == "generate" {
         let generated_secret = generate_random_secret();
-        if let Err(e) = update_secret_in_config(&generated_secret) {
+        if let Err(e) = update_secret_in_config("jwt", "secret", &generated_secret) {
             eprintln!("Warning: Failed to update Rocket.toml with generated secret: {}", e);
         }
         jwt_config.secret = generated_secret;
     }
 
+    if crypto_config.encryption_key == "generate" {
+        let generated_key = generate_random_secret();
+        if let Err(e) = update_secret_in_config("crypto", "encryption_key", &generated_key) {
+            eprintln!("Warning: Failed to update Rocket.toml with generated encryption key: {}", e);
+        }
+        crypto_config.encryption_key = generated_key;
+    }
+
+    let events = EventBus::default();
+    let transport = create_transport(&wol_config);
+
     let allowed_origins = if cors_config.allow_origin == "*" {
         AllowedOrigins::all()
     } else {
@@ -61,12 +102,34 @@ fn build_rocket() -> rocket::Rocket<rocket::Build> {
 
     rocket::build()
         .manage(jwt_config)
+        .manage(cors_config)
+        .manage(crypto_config.clone())
+        .manage(transport.clone())
+        .manage(wol_config.clone())
+        .manage(monitor_config.clone())
+        .manage(discovery_config)
+        .manage(ssh_config.clone())
+        .manage(Arc::new(WakeJobs::new(events.clone())))
+        .manage(events)
         .mount("/api", routes![api::user_api::join,api::user_api::login, 
             api::user_api::profile, api::user_api::check_first_user,
             api::device_api::get_devices, api::device_api::create_device,
             api::device_api::update_device, api::device_api::delete_device,
             api::device_api::move_device, api::device_api::get_device,
-            api::device_api::wake_device,
+            api::device_api::wake_device, api::device_api::wake_and_verify_device,
+            api::device_api::sleep_device, api::device_api::shutdown_device,
+            api::device_api::run_device_action, api::device_api::get_action_log,
+            api::device_api::get_power_status, api::device_api::control_power,
+            api::device_api::get_wake_job, api::device_api::resolve_neighbor,
+            api::device_api::lookup_mac,
+            api::admin_api::get_captured_packets, api::admin_api::clear_captured_packets,
+            api::event_api::events, api::ws_api::control_channel,
+            api::discovery_api::scan_network, api::discovery_api::import_devices,
+            api::group_api::get_groups, api::group_api::create_group, api::group_api::update_group,
+            api::group_api::delete_group, api::group_api::move_group, api::group_api::wake_group,
+            api::scene_api::get_scenes, api::scene_api::get_scene, api::scene_api::create_scene,
+            api::scene_api::update_scene, api::scene_api::delete_scene, api::scene_api::run_scene,
+            api::scene_api::get_scene_runs, api::scene_api::get_scene_run,
         ])
         .register("/", catchers![api::catcher::unauthorized, api::catcher::not_found])
         .attach(Db::init()) // DB 풀 초기화
@@ -78,17 +141,43 @@ fn build_rocket() -> rocket::Rocket<rocket::Build> {
             match is_exist_tables(&db).await {
                 Ok(exist) => if exist {
                     println!("DB Already created.");
-                    return Ok(rocket) 
+                } else {
+                    if let Err(e) = create_tables(&db).await {
+                        return Err(rocket)
+                    }
+                    println!("DB created.");
                 },
                 Err(_) => return Err(rocket) 
             }
-            
-            if let Err(e) = create_tables(&db).await {
+
+            if let Err(e) = migrate_tables(&db).await {
+                eprintln!("DB migration failed: {:?}", e);
                 return Err(rocket)
             }
-            println!("DB created.");
             Ok(rocket)
         }))
+        .attach(AdHoc::try_on_ignite("Power Resources", |rocket| async move {
+            // 전원 제어에 필요한 공용 자원은 DB 풀이 준비된 뒤 한 번만 생성
+            let pool = match Db::fetch(&rocket) {
+                Some(db) => db.0.clone(),
+                None => return Err(rocket)
+            };
+            let resources = PowerResources {
+                pool,
+                transport,
+                crypto: crypto_config,
+                wol: wol_config,
+                ssh: ssh_config,
+                monitor: monitor_config,
+                ipmi: ipmi_config,
+            };
+            Ok(rocket.manage(resources))
+        }))
+        .attach(AdHoc::on_liftoff("Device Monitor", |rocket| Box::pin(async move {
+            if let (Some(power), Some(events)) = (rocket.state::<PowerResources>(), rocket.state::<EventBus>()) {
+                start_monitor(power.pool.clone(), power.monitor.clone(), events.clone());
+            }
+        })))
         .attach(cors)
         .mount("/", FileServer::from("./static"))
 }
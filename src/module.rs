@@ -1,3 +1,22 @@
 pub mod magic_packet;
 pub mod jwt;
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod crypto;
+pub mod ethernet;
+pub mod transport;
+pub mod wake;
+pub mod probe;
+pub mod wake_job;
+pub mod monitor;
+pub mod events;
+pub mod neighbor;
+pub mod oui;
+pub mod mdns;
+pub mod discovery;
+pub mod host;
+pub mod ssh;
+pub mod action;
+pub mod power;
+pub mod group_wake;
+pub mod wake_plan;
+pub mod scene;
\ No newline at end of file
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Method;
+use rocket::serde::Serialize;
+use sqlx::SqlitePool;
+use crate::config::CryptoConfig;
+use crate::db::action::{ActionLog, ActionLogOperations, DeviceAction};
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::crypto::decrypt;
+use crate::module::host::resolve_host;
+use crate::module::probe::expand_probe_url;
+
+pub const ACTION_METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+// 결과에 포함하는 응답 본문의 최대 길이
+const MAX_RESPONSE_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ActionResult {
+    pub device_id: i64,
+    pub action: String,
+    pub method: String,
+    pub url: String,
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub latency_ms: i64,
+    pub body: Option<String>,
+    pub error: Option<String>,
+}
+
+// 저장된 헤더 JSON 객체를 복호화
+pub fn decrypt_headers(crypto: &CryptoConfig, action: &DeviceAction) -> Result<BTreeMap<String, String>, SystemError> {
+    match &action.headers {
+        Some(encrypted) => serde_json::from_str(&decrypt(crypto, encrypted)?)
+            .map_err(|_| SystemError::APIError(500, 0, "Invalid stored action headers".to_string())),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+// 헤더 이름/값이 HTTP 헤더로 사용 가능한지
+pub fn is_valid_header(name: &str, value: &str) -> bool {
+    HeaderName::from_bytes(name.as_bytes()).is_ok() && HeaderValue::from_str(value).is_ok()
+}
+
+// URL 템플릿의 {ip}, {mac}, {name}을 장치 값으로 치환
+// 장치 이름의 공백, /, ?, # 등이 URL 구조를 바꾸지 않도록 {mac}, {name} 값은 퍼센트 인코딩
+pub fn expand_url_template(template: &str, device: &Device, ip: Option<IpAddr>) -> Result<String, String> {
+    Ok(expand_probe_url(template, ip)?
+        .replace("{mac}", &percent_encode(&device.mac))
+        .replace("{name}", &percent_encode(&device.name)))
+}
+
+// 본문 템플릿의 {ip}, {mac}, {name}을 장치 값으로 치환 (본문은 인코딩하지 않음)
+pub fn expand_body_template(template: &str, device: &Device, ip: Option<IpAddr>) -> Result<String, String> {
+    Ok(expand_probe_url(template, ip)?
+        .replace("{mac}", &device.mac)
+        .replace("{name}", &device.name))
+}
+
+// RFC 3986의 unreserved 문자 외에는 UTF-8 바이트 단위로 %XX 인코딩
+fn percent_encode(value: &str) -> String {
+    value.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// 동작을 실행하고 결과를 action_log에 기록
+// 요청 전에 실패한 경우(잘못된 메서드, 템플릿, 헤더 등)도 실패로 기록한 뒤 에러 반환
+pub async fn run_and_log_action(pool: &SqlitePool, crypto: &CryptoConfig, device: &Device, action: &DeviceAction)
+    -> Result<ActionResult, SystemError> {
+    let result = run_action(crypto, device, action).await;
+    let log = match &result {
+        Ok(result) => ActionLog {
+            device_id: device.id,
+            action_name: action.name.clone(),
+            success: result.success,
+            status_code: result.status_code.map(i64::from),
+            latency_ms: Some(result.latency_ms),
+            error: result.error.clone(),
+            ..Default::default()
+        },
+        Err(e) => ActionLog {
+            device_id: device.id,
+            action_name: action.name.clone(),
+            success: false,
+            error: Some(e.message()),
+            ..Default::default()
+        },
+    };
+    ActionLog::insert(pool, &log).await?;
+    result
+}
+
+// 동작 요청 실행 (요청 자체의 실패는 결과의 error로 반환)
+pub async fn run_action(crypto: &CryptoConfig, device: &Device, action: &DeviceAction) -> Result<ActionResult, SystemError> {
+    let method = Method::from_bytes(action.method.as_bytes())
+        .map_err(|_| SystemError::APIError(400, 0, format!("Invalid HTTP method: {}", action.method)))?;
+    let ip = resolve_host(&device.ip).await;
+    let url = expand_url_template(&action.url, device, ip).map_err(|e| SystemError::APIError(400, 0, e))?;
+    let body = match &action.body {
+        Some(body) => Some(expand_body_template(body, device, ip).map_err(|e| SystemError::APIError(400, 0, e))?),
+        None => None,
+    };
+    let mut headers = HeaderMap::new();
+    for (name, value) in decrypt_headers(crypto, action)? {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| SystemError::APIError(400, 0, format!("Invalid header name: {}", name)))?;
+        let value = HeaderValue::from_str(&value)
+            .map_err(|_| SystemError::APIError(400, 0, format!("Invalid header value for {}", name)))?;
+        headers.insert(name, value);
+    }
+
+    let started = Instant::now();
+    let mut request = reqwest::Client::new()
+        .request(method, &url)
+        .headers(headers)
+        .timeout(Duration::from_millis(action.timeout_ms.max(1) as u64));
+    if let Some(body) = body {
+        request = request.body(body);
+    }
+
+    let mut result = ActionResult {
+        device_id: device.id,
+        action: action.name.clone(),
+        method: action.method.clone(),
+        url,
+        success: false,
+        status_code: None,
+        latency_ms: 0,
+        body: None,
+        error: None,
+    };
+    match request.send().await {
+        Ok(mut res) => {
+            let status = res.status();
+            result.status_code = Some(status.as_u16());
+            result.success = match action.expected_status {
+                Some(expected) => i64::from(status.as_u16()) == expected,
+                None => status.is_success(),
+            };
+            if !result.success {
+                result.error = Some(format!("Unexpected status: {}", status.as_u16()));
+            }
+            let mut body = Vec::new();
+            while body.len() < MAX_RESPONSE_BYTES {
+                match res.chunk().await {
+                    Ok(Some(chunk)) => body.extend_from_slice(&chunk[..chunk.len().min(MAX_RESPONSE_BYTES - body.len())]),
+                    _ => break,
+                }
+            }
+            result.body = Some(String::from_utf8_lossy(&body).to_string());
+        }
+        Err(e) => result.error = Some(format!("Request failed: {}", e)),
+    }
+    result.latency_ms = started.elapsed().as_millis() as i64;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device() -> Device {
+        Device { name: "Living room/PC #1".to_string(), mac: "AA:BB:CC:DD:EE:FF".to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn percent_encodes_reserved_and_non_ascii_bytes() {
+        assert_eq!(percent_encode("abc-._~019"), "abc-._~019");
+        assert_eq!(percent_encode("a b/c?d#e&f"), "a%20b%2Fc%3Fd%23e%26f");
+        assert_eq!(percent_encode("거실"), "%EA%B1%B0%EC%8B%A4");
+    }
+
+    #[test]
+    fn expands_url_template_with_encoded_values() {
+        let url = expand_url_template("http://{ip}/wake?mac={mac}&name={name}", &device(), Some("192.168.0.10".parse().unwrap()));
+        assert_eq!(url.unwrap(), "http://192.168.0.10/wake?mac=AA%3ABB%3ACC%3ADD%3AEE%3AFF&name=Living%20room%2FPC%20%231");
+    }
+
+    #[test]
+    fn expands_ipv6_in_brackets() {
+        let url = expand_url_template("http://{ip}:8080/", &device(), Some("fe80::1".parse().unwrap()));
+        assert_eq!(url.unwrap(), "http://[fe80::1]:8080/");
+    }
+
+    #[test]
+    fn url_template_requires_ip_only_when_used() {
+        assert!(expand_url_template("http://{ip}/", &device(), None).is_err());
+        assert_eq!(expand_url_template("http://hub.local/{name}", &device(), None).unwrap(), "http://hub.local/Living%20room%2FPC%20%231");
+    }
+}
@@ -0,0 +1,81 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use crate::config::CryptoConfig;
+use crate::error::SystemError;
+
+const NONCE_LEN: usize = 12;
+
+// 설정의 암호화 키(64자리 hex)를 AES-256 키로 변환
+fn cipher(config: &CryptoConfig) -> Result<Aes256Gcm, SystemError> {
+    let key = &config.encryption_key;
+    // 문자 경계에서 자르지 않도록 ASCII인지 먼저 확인
+    if key.len() != 64 || !key.is_ascii() {
+        return Err(SystemError::APIError(500, 0, "Invalid encryption key (expected 64 hex characters)".to_string()));
+    }
+
+    let mut bytes = [0u8; 32];
+    for i in 0..32 {
+        bytes[i] = u8::from_str_radix(&key[i*2..i*2+2], 16)
+            .map_err(|_| SystemError::APIError(500, 0, "Invalid encryption key hex value".to_string()))?;
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)))
+}
+
+// 평문을 암호화하여 base64(nonce + ciphertext) 형태로 반환
+pub fn encrypt(config: &CryptoConfig, plain: &str) -> Result<String, SystemError> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(config)?.encrypt(&nonce, plain.as_bytes())
+        .map_err(|_| SystemError::APIError(500, 0, "Failed to encrypt value".to_string()))?;
+
+    let mut data = nonce.to_vec();
+    data.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(data))
+}
+
+// encrypt로 암호화된 값을 복호화
+pub fn decrypt(config: &CryptoConfig, encrypted: &str) -> Result<String, SystemError> {
+    let data = STANDARD.decode(encrypted)
+        .map_err(|_| SystemError::APIError(500, 0, "Failed to decode encrypted value".to_string()))?;
+    if data.len() < NONCE_LEN {
+        return Err(SystemError::APIError(500, 0, "Encrypted value is too short".to_string()));
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plain = cipher(config)?.decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| SystemError::APIError(500, 0, "Failed to decrypt value (encryption key changed?)".to_string()))?;
+    String::from_utf8(plain)
+        .map_err(|_| SystemError::APIError(500, 0, "Decrypted value is not valid UTF-8".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(key: &str) -> CryptoConfig {
+        CryptoConfig { encryption_key: key.to_string() }
+    }
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let config = config(&"0123456789abcdef".repeat(4));
+        let encrypted = encrypt(&config, "secret 비밀번호").unwrap();
+        assert_ne!(encrypted, "secret 비밀번호");
+        assert_eq!(decrypt(&config, &encrypted).unwrap(), "secret 비밀번호");
+    }
+
+    #[test]
+    fn decrypt_with_other_key_fails() {
+        let encrypted = encrypt(&config(&"0".repeat(64)), "secret").unwrap();
+        assert!(decrypt(&config(&"1".repeat(64)), &encrypted).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(encrypt(&config("abcd"), "secret").is_err());
+        assert!(encrypt(&config(&"g".repeat(64)), "secret").is_err());
+        // 바이트 길이는 64지만 멀티바이트 문자가 포함된 키 (패닉 없이 에러)
+        assert!(encrypt(&config(&format!("{}가", "0".repeat(61))), "secret").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+use rocket::futures::{stream, StreamExt};
+use rocket::serde::Serialize;
+use rocket::tokio::task::spawn_blocking;
+use crate::config::DiscoveryConfig;
+use crate::error::SystemError;
+use crate::module::mdns;
+use crate::module::neighbor::{neighbor_table, NeighborEntry};
+use crate::module::oui::lookup_vendor;
+use crate::module::probe::is_reachable;
+
+// 검색으로 찾은 호스트
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct DiscoveredHost {
+    pub ip: String,
+    pub mac: Option<String>,
+    pub hostname: Option<String>,
+    pub vendor: Option<String>,
+    pub interface: Option<String>,
+}
+
+// 검색 대상 IPv4 대역
+#[derive(Debug, Clone, Copy)]
+pub struct Ipv4Network {
+    pub address: Ipv4Addr,
+    pub prefix: u8,
+}
+
+impl Ipv4Network {
+    // "192.168.0.0/24" 형식 파싱 (접두사 생략시 단일 호스트)
+    pub fn parse(cidr: &str) -> Result<Self, SystemError> {
+        let invalid = || SystemError::APIError(400, 0, format!("Invalid CIDR: {}", cidr));
+        let (address, prefix) = match cidr.trim().split_once('/') {
+            Some((address, prefix)) => (address, prefix.parse::<u8>().map_err(|_| invalid())?),
+            None => (cidr.trim(), 32),
+        };
+        let address = address.parse::<Ipv4Addr>().map_err(|_| invalid())?;
+        if prefix > 32 {
+            return Err(invalid());
+        }
+        Ok(Self { address, prefix })
+    }
+
+    fn mask(self) -> u32 {
+        if self.prefix == 0 { 0 } else { u32::MAX << (32 - self.prefix) }
+    }
+
+    pub fn host_count(self) -> u64 {
+        let size = 1u64 << (32 - self.prefix);
+        if self.prefix >= 31 { size } else { size - 2 }
+    }
+
+    // 호스트 수가 max_hosts 이하가 되도록 접두사를 늘려 주소가 속한 하위 대역으로 줄임 (max_hosts가 0이면 None)
+    pub fn clamp(self, max_hosts: u64) -> Option<Self> {
+        if max_hosts == 0 {
+            return None;
+        }
+        let mut network = self;
+        while network.host_count() > max_hosts && network.prefix < 32 {
+            network.prefix += 1;
+        }
+        Some(network)
+    }
+
+    // 네트워크/브로드캐스트 주소를 제외한 호스트 주소 목록 (/31, /32는 전체)
+    pub fn hosts(self) -> Vec<Ipv4Addr> {
+        let network = u32::from(self.address) & self.mask();
+        let broadcast = network | !self.mask();
+        let (first, last) = if self.prefix >= 31 { (network, broadcast) } else { (network + 1, broadcast - 1) };
+        (first..=last).map(Ipv4Addr::from).collect()
+    }
+}
+
+// 호스트의 네트워크 인터페이스에 연결된 IPv4 대역 목록
+// 컨테이너/VM/VPN용 가상 인터페이스는 제외하고, 전체 호스트 수가 max_hosts를 넘지 않도록 큰 대역은
+// 인터페이스 주소 주변의 하위 대역으로 줄임 (docker0의 /16 등으로 검색 전체가 거부되지 않도록)
+pub fn interface_networks(max_hosts: usize) -> Result<Vec<Ipv4Network>, SystemError> {
+    let addrs = if_addrs::get_if_addrs()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to list network interfaces: {}", e)))?;
+    let networks = addrs.into_iter()
+        .filter(|iface| !iface.is_loopback() && !is_virtual_interface(&iface.name))
+        .filter_map(|iface| match iface.addr {
+            if_addrs::IfAddr::V4(addr) if !addr.ip.is_link_local() => Some(Ipv4Network {
+                address: addr.ip,
+                prefix: u32::from(addr.netmask).leading_ones() as u8,
+            }),
+            _ => None,
+        });
+
+    let mut remaining = max_hosts as u64;
+    let mut clamped = Vec::new();
+    for network in networks {
+        let Some(network) = network.clamp(remaining) else { break };
+        remaining -= network.host_count();
+        clamped.push(network);
+    }
+    Ok(clamped)
+}
+
+// 컨테이너 브리지, 가상 머신, VPN 터널 등 LAN 장치가 없는 인터페이스 (이름 접두사로 판단)
+fn is_virtual_interface(name: &str) -> bool {
+    const PREFIXES: [&str; 18] = ["docker", "br-", "veth", "virbr", "vmnet", "vboxnet", "lxcbr", "lxdbr", "podman", "cni",
+                                  "flannel", "cali", "tun", "tap", "wg", "tailscale", "zt", "utun"];
+    // 윈도우 Hyper-V의 "vEthernet (...)"도 veth로 걸러짐
+    let name = name.to_lowercase();
+    PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+// 지정한 대역(미지정시 인터페이스 대역 전체)의 호스트를 확인하여 응답한 호스트 정보를 반환
+pub async fn scan(networks: &[Ipv4Network], config: &DiscoveryConfig) -> Result<Vec<DiscoveredHost>, SystemError> {
+    let total: u64 = networks.iter().map(|network| network.host_count()).sum();
+    if total > config.max_hosts as u64 {
+        return Err(SystemError::APIError(400, 0,
+            format!("Scan range too large: {} hosts (max {})", total, config.max_hosts)));
+    }
+
+    let local: Vec<IpAddr> = if_addrs::get_if_addrs()
+        .map(|addrs| addrs.into_iter().map(|iface| iface.ip()).collect())
+        .unwrap_or_default();
+    let mut targets: Vec<Ipv4Addr> = networks.iter().flat_map(|network| network.hosts()).collect();
+    targets.sort();
+    targets.dedup();
+    targets.retain(|ip| !local.contains(&IpAddr::V4(*ip)));
+
+    // 응답 확인 (응답하지 않는 호스트도 ARP 요청은 발생하므로 이후 이웃 테이블에 나타날 수 있음)
+    let wait = Duration::from_millis(config.timeout_ms);
+    let alive: Vec<(Ipv4Addr, bool)> = stream::iter(targets)
+        .map(|ip| async move { (ip, is_reachable(IpAddr::V4(ip), &config.tcp_ports, wait).await) })
+        .buffer_unordered(config.concurrency.max(1))
+        .collect()
+        .await;
+
+    let neighbors: HashMap<Ipv4Addr, NeighborEntry> = neighbor_table(false).await?
+        .into_iter()
+        .filter_map(|entry| Some((entry.ip.parse::<Ipv4Addr>().ok()?, entry)))
+        .collect();
+
+    let mut found: Vec<Ipv4Addr> = alive.into_iter()
+        .filter(|(ip, alive)| *alive || neighbors.contains_key(ip))
+        .map(|(ip, _)| ip)
+        .collect();
+    found.sort();
+
+    let hostnames: Vec<Option<String>> = stream::iter(found.clone())
+        .map(|ip| lookup_hostname(ip, config.mdns, wait))
+        .buffered(config.concurrency.max(1))
+        .collect()
+        .await;
+
+    Ok(found.into_iter().zip(hostnames).map(|(ip, hostname)| {
+        let neighbor = neighbors.get(&ip);
+        let mac = neighbor.map(|entry| entry.mac.clone());
+        DiscoveredHost {
+            ip: ip.to_string(),
+            vendor: mac.as_deref().and_then(lookup_vendor),
+            mac,
+            hostname,
+            interface: neighbor.and_then(|entry| entry.interface.clone()),
+        }
+    }).collect())
+}
+
+// 역방향 DNS 조회 후 실패하면 mDNS로 조회
+async fn lookup_hostname(ip: Ipv4Addr, use_mdns: bool, wait: Duration) -> Option<String> {
+    let reverse = spawn_blocking(move || dns_lookup::lookup_addr(&IpAddr::V4(ip)).ok()).await.ok().flatten()
+        // 이름이 없으면 IP 문자열을 그대로 반환하는 환경이 있음
+        .filter(|name| name.parse::<IpAddr>().is_err());
+    if reverse.is_some() || !use_mdns {
+        return reverse;
+    }
+    mdns::reverse_lookup(ip, wait).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_large_network_around_address() {
+        let network = Ipv4Network::parse("172.17.5.1/16").unwrap().clamp(1024).unwrap();
+        assert_eq!(network.prefix, 22);
+        assert_eq!(network.host_count(), 1022);
+        assert!(network.hosts().contains(&Ipv4Addr::new(172, 17, 5, 1)));
+
+        let network = Ipv4Network::parse("192.168.0.10/24").unwrap();
+        assert_eq!(network.clamp(1024).unwrap().prefix, 24);
+        assert_eq!(network.clamp(1).unwrap().prefix, 32);
+        assert!(network.clamp(0).is_none());
+    }
+
+    #[test]
+    fn detects_virtual_interfaces() {
+        for name in ["docker0", "br-1a2b3c", "veth12ab", "virbr0", "vEthernet (Default Switch)", "tailscale0", "wg0"] {
+            assert!(is_virtual_interface(name), "{} should be skipped", name);
+        }
+        for name in ["eth0", "enp3s0", "wlan0", "en0", "Ethernet", "Wi-Fi"] {
+            assert!(!is_virtual_interface(name), "{} should be scanned", name);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::SystemError;
+
+// Wake-on-LAN 전용 EtherType
+pub const WOL_ETHER_TYPE: u16 = 0x0842;
+// 802.1Q VLAN 태그 EtherType
+pub const VLAN_ETHER_TYPE: u16 = 0x8100;
+pub const BROADCAST_MAC: [u8; 6] = [0xFF; 6];
+
+// 이더넷 프레임 생성 (목적지 MAC + 출발지 MAC + [802.1Q 태그] + EtherType 0x0842 + 매직패킷)
+pub fn build_ethernet_frame(src_mac: [u8; 6], dst_mac: [u8; 6], vlan_id: Option<u16>, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(18 + payload.len());
+    frame.extend_from_slice(&dst_mac);
+    frame.extend_from_slice(&src_mac);
+    if let Some(vlan_id) = vlan_id {
+        // PCP/DEI는 0, 하위 12비트가 VLAN ID
+        frame.extend_from_slice(&VLAN_ETHER_TYPE.to_be_bytes());
+        frame.extend_from_slice(&(vlan_id & 0x0FFF).to_be_bytes());
+    }
+    frame.extend_from_slice(&WOL_ETHER_TYPE.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+// 지정한 인터페이스로 매직패킷을 raw 이더넷 프레임(브로드캐스트)으로 전송
+// AF_PACKET 소켓을 사용하므로 리눅스에서만 동작하며 CAP_NET_RAW 권한이 필요함
+#[cfg(target_os = "linux")]
+pub fn send_raw_magic_packet(interface: &str, vlan_id: Option<u16>, payload: &[u8]) -> Result<(), SystemError> {
+    use std::ffi::CString;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    let src_mac = read_interface_mac(interface)?;
+    let frame = build_ethernet_frame(src_mac, BROADCAST_MAC, vlan_id, payload);
+
+    let if_name = CString::new(interface)
+        .map_err(|_| SystemError::APIError(400, 0, "Invalid interface name".to_string()))?;
+    let if_index = unsafe { libc::if_nametoindex(if_name.as_ptr()) };
+    if if_index == 0 {
+        return Err(SystemError::APIError(400, 0, format!("Network interface not found: {}", interface)));
+    }
+
+    // 송신 전용이므로 protocol 0으로 열어 수신 큐에 프레임이 쌓이지 않도록 함
+    let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW, 0) };
+    if fd < 0 {
+        return Err(SystemError::APIError(500, 0, format!(
+            "Failed to open raw socket (CAP_NET_RAW required): {}", std::io::Error::last_os_error())));
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut addr: libc::sockaddr_ll = unsafe { std::mem::zeroed() };
+    addr.sll_family = libc::AF_PACKET as u16;
+    addr.sll_protocol = WOL_ETHER_TYPE.to_be();
+    addr.sll_ifindex = if_index as i32;
+    addr.sll_halen = 6;
+    addr.sll_addr[..6].copy_from_slice(&BROADCAST_MAC);
+
+    let sent = unsafe {
+        libc::sendto(
+            socket.as_raw_fd(),
+            frame.as_ptr() as *const libc::c_void,
+            frame.len(),
+            0,
+            &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+        )
+    };
+    if sent < 0 {
+        return Err(SystemError::APIError(500, 0, format!(
+            "Failed to send raw ethernet frame: {}", std::io::Error::last_os_error())));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn send_raw_magic_packet(_interface: &str, _vlan_id: Option<u16>, _payload: &[u8]) -> Result<(), SystemError> {
+    Err(SystemError::APIError(400, 0, "Raw ethernet transport is only supported on Linux".to_string()))
+}
+
+// 인터페이스의 하드웨어 주소 조회
+#[cfg(target_os = "linux")]
+fn read_interface_mac(interface: &str) -> Result<[u8; 6], SystemError> {
+    if interface.contains('/') {
+        return Err(SystemError::APIError(400, 0, "Invalid interface name".to_string()));
+    }
+    let path = format!("/sys/class/net/{}/address", interface);
+    let address = std::fs::read_to_string(&path)
+        .map_err(|_| SystemError::APIError(400, 0, format!("Network interface not found: {}", interface)))?;
+    crate::module::magic_packet::parse_mac_address(address.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+    #[test]
+    fn builds_untagged_frame() {
+        let frame = build_ethernet_frame(SRC, BROADCAST_MAC, None, &[1, 2, 3]);
+        assert_eq!(&frame[..6], &BROADCAST_MAC);
+        assert_eq!(&frame[6..12], &SRC);
+        assert_eq!(&frame[12..14], &[0x08, 0x42]);
+        assert_eq!(&frame[14..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn builds_802_1q_tagged_frame() {
+        let frame = build_ethernet_frame(SRC, BROADCAST_MAC, Some(100), &[1, 2, 3]);
+        assert_eq!(frame.len(), 18 + 3);
+        assert_eq!(&frame[12..14], &[0x81, 0x00]);
+        // PCP/DEI 0, VLAN ID 100 (0x064)
+        assert_eq!(&frame[14..16], &[0x00, 0x64]);
+        assert_eq!(&frame[16..18], &[0x08, 0x42]);
+        assert_eq!(&frame[18..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn vlan_id_is_masked_to_12_bits() {
+        let frame = build_ethernet_frame(SRC, BROADCAST_MAC, Some(0xF00A), &[]);
+        assert_eq!(&frame[14..16], &[0x00, 0x0A]);
+    }
+}
@@ -0,0 +1,87 @@
+use rocket::serde::Serialize;
+use rocket::tokio::sync::broadcast;
+use crate::module::power::PowerState;
+use crate::module::wake::WakeReport;
+use crate::module::wake_job::WakeJob;
+
+// 구독자가 따라오지 못할 때 보관하는 최대 이벤트 수 (초과시 오래된 이벤트부터 누락)
+const EVENT_BUFFER_SIZE: usize = 256;
+
+// 열려있는 모든 클라이언트에 전달되는 장치 관련 이벤트
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde", tag = "type", rename_all = "snake_case")]
+pub enum DeviceEvent {
+    DeviceCreated { device_id: i64 },
+    DeviceUpdated { device_id: i64 },
+    DeviceDeleted { device_id: i64 },
+    DeviceMoved { device_id: i64 },
+    // 매직패킷 전송 결과
+    Wake { report: WakeReport },
+    // 웨이크 후 응답 확인 작업의 상태 변경
+    WakeJob { job: Box<WakeJob> },
+    // Sleep-on-LAN 패킷 전송 결과
+    Sleep { report: WakeReport },
+    // 사용자 정의 HTTP 동작 실행
+    ActionRun { device_id: i64, action: String, success: bool },
+    // 전원 드라이버로 켜기/끄기 요청
+    Power { device_id: i64, operation: String, state: PowerState },
+    // SSH 원격 종료 명령 실행 (출력은 요청한 클라이언트에만 반환)
+    Shutdown { device_id: i64, exit_code: Option<u32> },
+    // 그룹 웨이크 중 구성원 한 대의 전원 켜기 결과
+    GroupWake { group_id: i64, device_id: i64, success: bool, error: Option<String> },
+    // 씬 실행 상태 변경 (running, succeeded, failed)
+    SceneRun { scene_id: i64, run_id: i64, status: String },
+    // 씬의 notify 단계
+    SceneNotification { scene_id: i64, run_id: i64, message: String },
+    // 상태 모니터링 결과 변경 (unknown, online, offline)
+    StatusChanged { device_id: i64, status: String },
+    // 헬스 체크 결과 변경 (up, down)
+    ProbeChanged { device_id: i64, probe_id: i64, status: String },
+}
+
+impl DeviceEvent {
+    // SSE의 event 필드로 사용하는 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeviceEvent::DeviceCreated { .. } => "device_created",
+            DeviceEvent::DeviceUpdated { .. } => "device_updated",
+            DeviceEvent::DeviceDeleted { .. } => "device_deleted",
+            DeviceEvent::DeviceMoved { .. } => "device_moved",
+            DeviceEvent::Wake { .. } => "wake",
+            DeviceEvent::WakeJob { .. } => "wake_job",
+            DeviceEvent::Sleep { .. } => "sleep",
+            DeviceEvent::ActionRun { .. } => "action_run",
+            DeviceEvent::Power { .. } => "power",
+            DeviceEvent::Shutdown { .. } => "shutdown",
+            DeviceEvent::GroupWake { .. } => "group_wake",
+            DeviceEvent::SceneRun { .. } => "scene_run",
+            DeviceEvent::SceneNotification { .. } => "scene_notification",
+            DeviceEvent::StatusChanged { .. } => "status_changed",
+            DeviceEvent::ProbeChanged { .. } => "probe_changed",
+        }
+    }
+}
+
+// 이벤트 발행/구독 채널 (Rocket State로 관리되며, 복제해도 같은 채널을 공유)
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DeviceEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self { sender }
+    }
+}
+
+impl EventBus {
+    // 구독자가 없으면 이벤트는 버려짐
+    pub fn publish(&self, event: DeviceEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DeviceEvent> {
+        self.sender.subscribe()
+    }
+}
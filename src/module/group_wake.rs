@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::power::PowerResources;
+use crate::module::wake_plan::{dependencies_for, power_on_step, wake_dependencies, WakeStep};
+
+// 그룹 구성원을 순서대로 켜는 작업을 백그라운드로 시작
+// 장치 사이에 stagger만큼 대기하여 여러 장치가 동시에 기동하지 않도록 함 (결과는 group_wake 이벤트로 전달)
+// 구성원에 의존 장치가 있다면 /device/wake/<id>와 같이 의존 장치를 먼저 켜서 응답을 확인한 뒤 켜고,
+// 의존 장치가 실패하거나 응답하지 않으면 그 구성원은 켜지 않음
+// (의존 장치가 다른 구성원이라면 먼저 켜고, 이미 켠 장치는 다시 켜지 않음)
+pub fn start_group_wake(resources: PowerResources, events: EventBus, group_id: i64, devices: Vec<Device>, stagger: Duration) {
+    rocket::tokio::spawn(async move {
+        let ctx = resources.context();
+        let mut woken = HashMap::new();
+        for device in &devices {
+            if woken.contains_key(&device.id) {
+                continue;
+            }
+            if !woken.is_empty() && !stagger.is_zero() {
+                rocket::tokio::time::sleep(stagger).await;
+            }
+            let dependencies = match dependencies_for(&resources.pool, device.id).await {
+                Ok(dependencies) => dependencies,
+                Err(e) => {
+                    publish_result(&events, group_id, device.id, Err(e));
+                    continue;
+                }
+            };
+            let result = wake_dependencies(&ctx, &dependencies, &mut woken, |step| {
+                if let Some(report) = &step.report {
+                    events.publish(report.event());
+                }
+                if devices.iter().any(|member| member.id == step.device_id) {
+                    publish_result(&events, group_id, step.device_id, step_result(step));
+                }
+            }).await;
+            if let Err(step) = result {
+                publish_result(&events, group_id, device.id, Err(SystemError::APIError(502, 0, step.blocked_reason())));
+                continue;
+            }
+
+            let step = power_on_step(&ctx, device, false).await;
+            publish_result(&events, group_id, device.id, step_result(&step));
+            woken.insert(device.id, step);
+        }
+    });
+}
+
+fn step_result(step: &WakeStep) -> Result<(), SystemError> {
+    if step.status.blocks_dependents() {
+        Err(SystemError::APIError(502, 0, step.error.clone().unwrap_or_default()))
+    } else {
+        Ok(())
+    }
+}
+
+fn publish_result(events: &EventBus, group_id: i64, device_id: i64, result: Result<(), SystemError>) {
+    let error = result.err().map(|e| e.message());
+    events.publish(DeviceEvent::GroupWake { group_id, device_id, success: error.is_none(), error });
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use lazy_static::lazy_static;
+use rocket::tokio::net::lookup_host;
+use rocket::tokio::time::timeout;
+use crate::db::device::Device;
+
+// 호스트 이름 조회에 허용되는 최대 시간
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(2);
+// 화면 표시용 조회 결과를 재사용하는 시간
+const RESOLVE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    // 호스트 이름 -> (조회 결과, 조회 시각)
+    static ref RESOLVE_CACHE: Mutex<HashMap<String, (Option<IpAddr>, Instant)>> = Mutex::new(HashMap::new());
+}
+
+// 장치 주소 필드 값 (IPv4, IPv6 또는 호스트 이름)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Host {
+    Ip(IpAddr),
+    Name(String),
+}
+
+impl Host {
+    // 빈 값이나 형식에 맞지 않는 값은 None
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(ip) = value.parse::<IpAddr>() {
+            return Some(Host::Ip(ip));
+        }
+        is_valid_hostname(value).then(|| Host::Name(value.trim_end_matches('.').to_lowercase()))
+    }
+}
+
+// RFC 1123 호스트 이름 검증 (영문/숫자/하이픈으로 된 1~63자 라벨, 전체 253자 이하)
+// 숫자로만 이루어진 이름은 잘못 입력한 IPv4 주소일 가능성이 높으므로 거부
+fn is_valid_hostname(value: &str) -> bool {
+    let name = value.strip_suffix('.').unwrap_or(value);
+    if name.is_empty() || name.len() > 253 {
+        return false;
+    }
+    let labels_valid = name.split('.').all(|label| {
+        !label.is_empty() && label.len() <= 63
+            && !label.starts_with('-') && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+    labels_valid && !name.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+// 주소 필드 값을 IP로 변환 (호스트 이름은 DNS 조회, IPv4 결과 우선)
+pub async fn resolve_host(value: &str) -> Option<IpAddr> {
+    match Host::parse(value)? {
+        Host::Ip(ip) => Some(ip),
+        Host::Name(name) => {
+            let addrs: Vec<IpAddr> = timeout(RESOLVE_TIMEOUT, lookup_host((name.as_str(), 0))).await.ok()?.ok()?
+                .map(|addr| addr.ip())
+                .collect();
+            addrs.iter().find(|ip| ip.is_ipv4()).or(addrs.first()).copied()
+        }
+    }
+}
+
+// 목록 조회 등 표시용 주소 변환 (호스트 이름은 RESOLVE_CACHE_TTL 동안 이전 조회 결과를 재사용)
+// 웨이크/상태 확인처럼 현재 주소가 필요한 곳에서는 resolve_host 사용
+pub async fn resolve_host_cached(value: &str) -> Option<IpAddr> {
+    let name = match Host::parse(value)? {
+        Host::Ip(ip) => return Some(ip),
+        Host::Name(name) => name,
+    };
+    if let Some((ip, resolved_at)) = RESOLVE_CACHE.lock().unwrap().get(&name)
+        && resolved_at.elapsed() < RESOLVE_CACHE_TTL {
+        return *ip;
+    }
+    let ip = resolve_host(&name).await;
+    let mut cache = RESOLVE_CACHE.lock().unwrap();
+    cache.retain(|_, (_, resolved_at)| resolved_at.elapsed() < RESOLVE_CACHE_TTL);
+    cache.insert(name, (ip, Instant::now()));
+    ip
+}
+
+// 장치 주소가 호스트 이름이면 조회한 IP로 바꾼 복사본 반환 (조회 실패시 주소를 비움)
+pub async fn with_resolved_ip(device: &Device) -> Device {
+    let ip = match Host::parse(&device.ip) {
+        Some(Host::Ip(_)) | None => return device.clone(),
+        Some(Host::Name(_)) => resolve_host(&device.ip).await,
+    };
+    Device { ip: ip.map(|ip| ip.to_string()).unwrap_or_default(), ..device.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ip_addresses() {
+        assert_eq!(Host::parse(" 192.168.0.10 "), Some(Host::Ip("192.168.0.10".parse().unwrap())));
+        assert_eq!(Host::parse("fe80::1"), Some(Host::Ip("fe80::1".parse().unwrap())));
+    }
+
+    #[test]
+    fn parses_and_normalizes_hostnames() {
+        assert_eq!(Host::parse("My-PC.local."), Some(Host::Name("my-pc.local".to_string())));
+        assert_eq!(Host::parse("nas"), Some(Host::Name("nas".to_string())));
+        assert_eq!(Host::parse(&format!("{}.lan", "a".repeat(63))), Some(Host::Name(format!("{}.lan", "a".repeat(63)))));
+    }
+
+    #[test]
+    fn rejects_invalid_hosts() {
+        for value in ["", "  ", "192.168.0.256", "1.2.3", "-pc.local", "pc-.local", "pc..local", "my_pc", "호스트",
+                      &"a".repeat(64), &format!("{}.lan", "a.".repeat(130))] {
+            assert_eq!(Host::parse(value), None, "{} should be rejected", value);
+        }
+    }
+}
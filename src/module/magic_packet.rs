@@ -1,12 +1,201 @@
-use std::net::UdpSocket;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use rocket::tokio::net::UdpSocket;
+use crate::db::device::Device;
 use crate::error::SystemError;
+use crate::module::transport::PacketTransport;
 
-// MAC 주소를 바이트 배열로 파싱
+// 기본 WOL 포트 (discard 포트 9, echo 포트 7도 많이 사용됨)
+pub const DEFAULT_WOL_PORT: u16 = 9;
+// 넷마스크가 지정되지 않은 경우 사용하는 기본값 (/24)
+pub const DEFAULT_NETMASK: Ipv4Addr = Ipv4Addr::new(255, 255, 255, 0);
+// IPv6에는 브로드캐스트가 없으므로 링크 로컬 전체 노드 멀티캐스트 주소를 사용
+pub const IPV6_ALL_NODES: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1);
+
+// 매직패킷 전송 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeTransport {
+    // UDP 브로드캐스트
+    Udp,
+    // raw 이더넷 프레임 (EtherType 0x0842)
+    Ethernet,
+    // UDP와 raw 이더넷 모두 전송
+    Both,
+}
+
+impl WakeTransport {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "udp" => Some(WakeTransport::Udp),
+            "ethernet" => Some(WakeTransport::Ethernet),
+            "both" => Some(WakeTransport::Both),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WakeTransport::Udp => "udp",
+            WakeTransport::Ethernet => "ethernet",
+            WakeTransport::Both => "both",
+        }
+    }
+
+    pub fn uses_udp(self) -> bool {
+        self != WakeTransport::Ethernet
+    }
+
+    pub fn uses_ethernet(self) -> bool {
+        self != WakeTransport::Udp
+    }
+}
+
+// 매직패킷 전송 대상 (브로드캐스트/멀티캐스트 주소, 포트, 송신 인터페이스, 전송 방식)
+#[derive(Debug, Clone)]
+pub struct MagicPacketTarget {
+    pub address: IpAddr,
+    pub port: u16,
+    pub interface: Option<String>,
+    pub transport: WakeTransport,
+    pub vlan_id: Option<u16>,
+}
+
+impl MagicPacketTarget {
+    // 장치 설정으로부터 전송 대상 결정
+    // 브로드캐스트 주소가 지정되지 않았다면 장치 IP와 넷마스크로 서브넷 브로드캐스트 주소를 계산
+    // (장치 IP가 IPv6라면 ff02::1)
+    pub fn from_device(device: &Device) -> Result<Self, SystemError> {
+        let address = match &device.broadcast {
+            Some(broadcast) => broadcast.parse::<IpAddr>()
+                .map_err(|_| SystemError::APIError(400, 0, "Invalid broadcast address".to_string()))?,
+            None => default_broadcast(&device.ip, device.netmask.as_deref())?,
+        };
+
+        let port = u16::try_from(device.port)
+            .ok().filter(|port| *port != 0)
+            .ok_or(SystemError::APIError(400, 0, "Invalid WOL port".to_string()))?;
+
+        let transport = WakeTransport::parse(&device.transport)
+            .ok_or(SystemError::APIError(400, 0, "Invalid wake transport".to_string()))?;
+        let vlan_id = match device.vlan_id {
+            Some(vlan_id) => Some(u16::try_from(vlan_id).ok().filter(|id| (1..=4094).contains(id))
+                .ok_or(SystemError::APIError(400, 0, "Invalid VLAN ID".to_string()))?),
+            None => None,
+        };
+
+        Ok(Self { address, port, interface: device.interface.clone(), transport, vlan_id })
+    }
+
+    // 목적지 소켓 주소 생성 (링크 로컬 IPv6 주소는 송신 인터페이스를 scope로 지정)
+    pub fn socket_addr(&self, ip: IpAddr) -> Result<SocketAddr, SystemError> {
+        match ip {
+            IpAddr::V4(_) => Ok(SocketAddr::new(ip, self.port)),
+            IpAddr::V6(ip) => {
+                let scope_id = if is_link_scoped(ip) {
+                    let interface = self.interface.as_deref()
+                        .ok_or(SystemError::APIError(400, 0, "IPv6 link-local target requires a network interface".to_string()))?;
+                    interface_index(interface)?
+                } else {
+                    0
+                };
+                Ok(SocketAddr::V6(SocketAddrV6::new(ip, self.port, 0, scope_id)))
+            }
+        }
+    }
+}
+
+// 인터페이스 scope가 필요한 IPv6 주소인지 (링크 로컬 유니캐스트 fe80::/10, 인터페이스/링크 로컬 멀티캐스트 ff01::/16, ff02::/16)
+fn is_link_scoped(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    (first & 0xffc0) == 0xfe80 || (first & 0xff0f) == 0xff01 || (first & 0xff0f) == 0xff02
+}
+
+// 인터페이스 이름(또는 숫자 scope id)을 인터페이스 인덱스로 변환
+pub fn interface_index(interface: &str) -> Result<u32, SystemError> {
+    if let Ok(index) = interface.parse::<u32>() {
+        return Ok(index);
+    }
+
+    let addrs = if_addrs::get_if_addrs()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to list network interfaces: {}", e)))?;
+    addrs.into_iter()
+        .find(|iface| iface.name == interface)
+        .and_then(|iface| iface.index)
+        .ok_or(SystemError::APIError(400, 0, format!("Network interface not found: {}", interface)))
+}
+
+// 서브넷 브로드캐스트 주소 계산 (ip | !netmask)
+pub fn subnet_broadcast(ip: Ipv4Addr, netmask: Ipv4Addr) -> Ipv4Addr {
+    Ipv4Addr::from(u32::from(ip) | !u32::from(netmask))
+}
+
+// 장치 IP가 IPv4라면 서브넷 브로드캐스트, IPv6라면 ff02::1, 그 외에는 전역 브로드캐스트 주소를 사용
+fn default_broadcast(ip: &str, netmask: Option<&str>) -> Result<IpAddr, SystemError> {
+    let ip = match ip.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => ip,
+        Ok(IpAddr::V6(_)) => return Ok(IpAddr::V6(IPV6_ALL_NODES)),
+        Err(_) => return Ok(IpAddr::V4(Ipv4Addr::BROADCAST)),
+    };
+    let netmask = match netmask {
+        Some(netmask) => netmask.parse::<Ipv4Addr>()
+            .map_err(|_| SystemError::APIError(400, 0, "Invalid netmask".to_string()))?,
+        None => DEFAULT_NETMASK,
+    };
+    Ok(IpAddr::V4(subnet_broadcast(ip, netmask)))
+}
+
+// 송신 인터페이스 지정값(IP 또는 인터페이스 이름)을 바인딩할 로컬 주소로 변환
+// IPv6는 인터페이스를 목적지 scope로 지정하므로 인터페이스 이름인 경우 미지정 주소(::)에 바인딩
+pub fn resolve_source_addr(interface: &str, ipv6: bool) -> Result<IpAddr, SystemError> {
+    if let Ok(ip) = interface.parse::<IpAddr>() {
+        return Ok(ip);
+    }
+    if ipv6 {
+        return Ok(IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+    }
+
+    let addrs = if_addrs::get_if_addrs()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to list network interfaces: {}", e)))?;
+    addrs.into_iter()
+        .filter(|iface| iface.name == interface)
+        .find_map(|iface| match iface.addr {
+            if_addrs::IfAddr::V4(addr) => Some(IpAddr::V4(addr.ip)),
+            _ => None,
+        })
+        .ok_or(SystemError::APIError(400, 0, format!("Network interface not found: {}", interface)))
+}
+
+// MAC 주소를 바이트 배열로 파싱 (대소문자 구분 없음)
+// AA:BB:CC:DD:EE:FF, AA-BB-CC-DD-EE-FF, aabb.ccdd.eeff(Cisco), aabbccddeeff 형식 지원
 pub fn parse_mac_address(mac: &str) -> Result<[u8; 6], SystemError> {
-    let mac_clean = mac.replace(":", "").replace("-", "");
+    let invalid = || SystemError::APIError(400, 0, "Invalid MAC address format".to_string());
+    let mac = mac.trim();
+    if !mac.is_ascii() {
+        return Err(invalid());
+    }
 
-    if mac_clean.len() != 12 {
-        return Err(SystemError::APIError(400, 0, "Invalid MAC address format".to_string()));
+    // 구분자별로 그룹 수와 그룹 길이가 맞아야 함 (구분자를 섞어 쓴 경우는 거부)
+    let groups: Vec<&str> = if mac.contains(':') {
+        mac.split(':').collect()
+    } else if mac.contains('-') {
+        mac.split('-').collect()
+    } else if mac.contains('.') {
+        mac.split('.').collect()
+    } else {
+        vec![mac]
+    };
+    let valid_grouping = match groups.len() {
+        6 => groups.iter().all(|g| g.len() == 2),
+        3 => groups.iter().all(|g| g.len() == 4) && mac.contains('.'),
+        1 => groups[0].len() == 12,
+        _ => false,
+    };
+    if !valid_grouping {
+        return Err(invalid());
+    }
+    // from_str_radix는 부호(+)를 허용하므로 16진수 문자만 있는지 먼저 확인
+    let mac_clean = groups.concat();
+    if !mac_clean.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SystemError::APIError(400, 0, "Invalid MAC address hex value".to_string()));
     }
 
     let mut bytes = [0u8; 6];
@@ -18,26 +207,219 @@ pub fn parse_mac_address(mac: &str) -> Result<[u8; 6], SystemError> {
     Ok(bytes)
 }
 
+// MAC 주소 저장 형식 (AA:BB:CC:DD:EE:FF)
+pub fn format_mac_address(bytes: &[u8; 6]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+// 지원하는 표기 중 하나로 입력된 MAC 주소를 저장 형식으로 변환
+pub fn normalize_mac_address(mac: &str) -> Result<String, SystemError> {
+    Ok(format_mac_address(&parse_mac_address(mac)?))
+}
+
+// SecureOn 비밀번호를 바이트 배열로 파싱
+// 4바이트(192.168.1.1 형식 또는 hex 8자리) 또는 6바이트(MAC 주소 형식 또는 hex 12자리)
+pub fn parse_secureon_password(password: &str) -> Result<Vec<u8>, SystemError> {
+    let invalid = || SystemError::APIError(400, 0, "Invalid SecureOn password format".to_string());
+
+    // parse/from_str_radix는 부호(+)를 허용하므로 숫자만 있는지 먼저 확인
+    let parts: Vec<&str> = password.split('.').collect();
+    if parts.len() == 4 {
+        return parts.iter()
+            .map(|part| match part.chars().all(|c| c.is_ascii_digit()) {
+                true => part.parse::<u8>().map_err(|_| invalid()),
+                false => Err(invalid()),
+            })
+            .collect();
+    }
+
+    let clean = password.replace(":", "").replace("-", "");
+    if (clean.len() != 8 && clean.len() != 12) || !clean.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    (0..clean.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&clean[i..i+2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
 // 매직패킷 생성 (6바이트 0xFF + MAC 주소 16회 반복 = 102바이트)
-pub fn create_magic_packet(mac_bytes: [u8; 6]) -> Vec<u8> {
-    let mut packet = Vec::with_capacity(102);
+// SecureOn 비밀번호가 있다면 패킷 끝에 4 또는 6바이트를 덧붙임
+pub fn create_magic_packet(mac_bytes: [u8; 6], secureon: Option<&[u8]>) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(108);
     packet.extend_from_slice(&[0xFF; 6]);
     for _ in 0..16 {
         packet.extend_from_slice(&mac_bytes);
     }
+    if let Some(password) = secureon {
+        packet.extend_from_slice(password);
+    }
     packet
 }
 
-pub fn send_magic_packet(mac: &str) -> Result<(), SystemError> {
-    let mac_bytes = parse_mac_address(mac)?;
-    let magic_packet = create_magic_packet(mac_bytes);
+// Sleep-on-LAN 패킷 생성 (MAC 주소 바이트 순서를 뒤집은 매직패킷)
+// 장치의 에이전트가 자신의 MAC을 뒤집은 패킷을 받으면 절전 모드로 전환
+pub fn create_sleep_packet(mac_bytes: [u8; 6]) -> Vec<u8> {
+    let mut reversed = mac_bytes;
+    reversed.reverse();
+    create_magic_packet(reversed, None)
+}
+
+// 매직패킷 목적지
+#[derive(Debug, Clone, Copy)]
+pub enum PacketDestination {
+    Udp(SocketAddr),
+    // 송신 인터페이스로 raw 이더넷 브로드캐스트
+    Ethernet,
+}
+
+// 생성된 매직패킷을 목적지 하나로 전송
+pub async fn send_magic_packet(transport: &dyn PacketTransport, packet: &[u8], destination: PacketDestination, target: &MagicPacketTarget)
+    -> Result<(), SystemError> {
+    match destination {
+        PacketDestination::Udp(address) => transport.send_udp(packet, address, target.interface.as_deref()).await,
+        PacketDestination::Ethernet => {
+            let interface = target.interface.as_deref()
+                .ok_or(SystemError::APIError(400, 0, "Raw ethernet transport requires a network interface".to_string()))?;
+            transport.send_ethernet(packet, interface, target.vlan_id).await
+        }
+    }
+}
+
+// UDP 소켓으로 패킷 전송 (interface 지정시 해당 인터페이스 주소로 바인딩)
+pub async fn send_udp_packet(payload: &[u8], target: SocketAddr, interface: Option<&str>) -> Result<(), SystemError> {
+    let bind_ip = match interface {
+        Some(interface) => resolve_source_addr(interface, target.is_ipv6())?,
+        None if target.is_ipv6() => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    };
 
-    let socket = UdpSocket::bind("0.0.0.0:0")
+    let socket = UdpSocket::bind(SocketAddr::new(bind_ip, 0)).await
         .map_err(|e| SystemError::APIError(500, 0, format!("Failed to bind UDP socket: {}", e)))?;
-    socket.set_broadcast(true)
-        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to set broadcast: {}", e)))?;
-    socket.send_to(&magic_packet, "255.255.255.255:9")
+    if target.is_ipv4() {
+        socket.set_broadcast(true)
+            .map_err(|e| SystemError::APIError(500, 0, format!("Failed to set broadcast: {}", e)))?;
+    }
+    socket.send_to(payload, target).await
         .map_err(|e| SystemError::APIError(500, 0, format!("Failed to send magic packet: {}", e)))?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAC: [u8; 6] = [0xAA, 0xBB, 0xCC, 0x0D, 0xEE, 0xFF];
+
+    #[test]
+    fn parses_supported_formats() {
+        assert_eq!(parse_mac_address("AA:BB:CC:0D:EE:FF").unwrap(), MAC);
+        assert_eq!(parse_mac_address("aa-bb-cc-0d-ee-ff").unwrap(), MAC);
+        assert_eq!(parse_mac_address("aabb.cc0d.eeff").unwrap(), MAC);
+        assert_eq!(parse_mac_address("AABBCC0DEEFF").unwrap(), MAC);
+        assert_eq!(parse_mac_address("  aa:bb:cc:0d:ee:ff  ").unwrap(), MAC);
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        for mac in ["", "AA:BB:CC:DD:EE", "AA:BB:CC:DD:EE:FF:00", "AA:BB-CC:DD:EE:FF", "A:BB:CC:DD:EE:FFF",
+                    "aabb-ccdd-eeff", "aabb.ccdd.eef", "GG:BB:CC:DD:EE:FF", "AABBCCDDEEF", "가가:BB:CC:DD:EE",
+                    "+a:+b:+c:+d:+e:+f", "+abb.ccdd.eeff"] {
+            assert!(parse_mac_address(mac).is_err(), "{} should be rejected", mac);
+        }
+    }
+
+    #[test]
+    fn normalizes_to_colon_upper_case() {
+        for mac in ["aa:bb:cc:0d:ee:ff", "AA-BB-CC-0D-EE-FF", "aabb.cc0d.eeff", "aabbcc0deeff"] {
+            assert_eq!(normalize_mac_address(mac).unwrap(), "AA:BB:CC:0D:EE:FF");
+        }
+        assert!(normalize_mac_address("not a mac").is_err());
+    }
+
+    #[test]
+    fn parses_secureon_passwords() {
+        assert_eq!(parse_secureon_password("192.168.1.1").unwrap(), vec![192, 168, 1, 1]);
+        assert_eq!(parse_secureon_password("0a0b0c0d").unwrap(), vec![0x0A, 0x0B, 0x0C, 0x0D]);
+        assert_eq!(parse_secureon_password("00:11:22:33:44:55").unwrap(), vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(parse_secureon_password("00-11-22-33-44-55").unwrap(), vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+    }
+
+    fn ipv6_device(ip: &str, interface: Option<&str>) -> Device {
+        Device {
+            ip: ip.to_string(),
+            port: 9,
+            interface: interface.map(str::to_string),
+            transport: "udp".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ipv6_device_defaults_to_all_nodes_multicast() {
+        let target = MagicPacketTarget::from_device(&ipv6_device("fe80::1", Some("3"))).unwrap();
+        assert_eq!(target.address, IpAddr::V6(IPV6_ALL_NODES));
+    }
+
+    #[test]
+    fn link_scoped_ipv6_target_uses_interface_scope() {
+        let target = MagicPacketTarget::from_device(&ipv6_device("fe80::1", Some("3"))).unwrap();
+        let SocketAddr::V6(addr) = target.socket_addr(target.address).unwrap() else { panic!("expected IPv6 address") };
+        assert_eq!(*addr.ip(), IPV6_ALL_NODES);
+        assert_eq!(addr.port(), 9);
+        assert_eq!(addr.scope_id(), 3);
+
+        let SocketAddr::V6(addr) = target.socket_addr("fe80::1".parse().unwrap()).unwrap() else { panic!("expected IPv6 address") };
+        assert_eq!(addr.scope_id(), 3);
+    }
+
+    #[test]
+    fn global_ipv6_target_has_no_scope() {
+        let target = MagicPacketTarget::from_device(&ipv6_device("2001:db8::1", None)).unwrap();
+        let SocketAddr::V6(addr) = target.socket_addr("2001:db8::1".parse().unwrap()).unwrap() else { panic!("expected IPv6 address") };
+        assert_eq!(addr.scope_id(), 0);
+    }
+
+    #[test]
+    fn link_scoped_ipv6_target_requires_interface() {
+        let target = MagicPacketTarget::from_device(&ipv6_device("fe80::1", None)).unwrap();
+        assert!(target.socket_addr(target.address).is_err());
+    }
+
+    #[test]
+    fn magic_packet_repeats_mac_after_sync_stream() {
+        let packet = create_magic_packet(MAC, None);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert!(packet[6..].chunks(6).all(|chunk| chunk == MAC));
+    }
+
+    #[test]
+    fn magic_packet_appends_secureon_password() {
+        let four = parse_secureon_password("192.168.1.1").unwrap();
+        let packet = create_magic_packet(MAC, Some(&four));
+        assert_eq!(packet.len(), 106);
+        assert_eq!(&packet[102..], &[192, 168, 1, 1]);
+
+        let six = parse_secureon_password("00:11:22:33:44:55").unwrap();
+        let packet = create_magic_packet(MAC, Some(&six));
+        assert_eq!(packet.len(), 108);
+        assert_eq!(&packet[..102], create_magic_packet(MAC, None).as_slice());
+        assert_eq!(&packet[102..], &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+    }
+
+    #[test]
+    fn sleep_packet_repeats_reversed_mac() {
+        let packet = create_sleep_packet(MAC);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert!(packet[6..].chunks(6).all(|chunk| chunk == [0xFF, 0xEE, 0x0D, 0xCC, 0xBB, 0xAA]));
+    }
+
+    #[test]
+    fn rejects_signed_secureon_passwords() {
+        for password in ["+a+b+c+d", "+0a0b0c0d0e", "+1.2.3.4", "1.2.3.256", "0a0b0c0g", "0a0b0c"] {
+            assert!(parse_secureon_password(password).is_err(), "{} should be rejected", password);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use rand_core::{OsRng, RngCore};
+use rocket::tokio::net::UdpSocket;
+use rocket::tokio::time::{timeout, Instant};
+
+const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+const TYPE_PTR: u16 = 12;
+// QU 비트를 설정한 IN 클래스 (응답을 유니캐스트로 요청)
+const CLASS_IN_UNICAST: u16 = 0x8001;
+
+// mDNS 역방향(PTR) 질의로 호스트 이름 조회 (예: 192.168.0.10 -> my-pc.local)
+// 5353이 아닌 포트에서 질의하면 응답자가 유니캐스트로 답하므로(RFC 6762 6.7) 별도 멀티캐스트 수신이 필요 없음
+pub async fn reverse_lookup(ip: Ipv4Addr, wait: Duration) -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await.ok()?;
+    let id = OsRng.next_u32() as u16;
+    let query = build_ptr_query(id, &reverse_name(ip));
+    socket.send_to(&query, SocketAddr::new(IpAddr::V4(MDNS_ADDR), MDNS_PORT)).await.ok()?;
+
+    let deadline = Instant::now() + wait;
+    let mut buf = [0u8; 1500];
+    loop {
+        let remaining = deadline.checked_duration_since(Instant::now())?;
+        let (len, _) = timeout(remaining, socket.recv_from(&mut buf)).await.ok()?.ok()?;
+        if let Some(name) = parse_ptr_answer(&buf[..len]) {
+            return Some(name.trim_end_matches('.').to_string());
+        }
+    }
+}
+
+fn reverse_name(ip: Ipv4Addr) -> String {
+    let [a, b, c, d] = ip.octets();
+    format!("{}.{}.{}.{}.in-addr.arpa", d, c, b, a)
+}
+
+fn build_ptr_query(id: u16, name: &str) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(64);
+    packet.extend_from_slice(&id.to_be_bytes());
+    // flags, qdcount=1, ancount, nscount, arcount
+    packet.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&TYPE_PTR.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN_UNICAST.to_be_bytes());
+    packet
+}
+
+// 응답 패킷에서 첫 번째 PTR 레코드의 대상 이름 추출
+fn parse_ptr_answer(packet: &[u8]) -> Option<String> {
+    let read_u16 = |pos: usize| packet.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+    let is_response = packet.get(2)? & 0x80 != 0;
+    if !is_response {
+        return None;
+    }
+    let questions = read_u16(4)?;
+    let answers = read_u16(6)?;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = read_name(packet, pos)?.1 + 4;
+    }
+    for _ in 0..answers {
+        pos = read_name(packet, pos)?.1;
+        let record_type = read_u16(pos)?;
+        let data_len = read_u16(pos + 8)? as usize;
+        let data_start = pos + 10;
+        if record_type == TYPE_PTR {
+            return read_name(packet, data_start).map(|(name, _)| name);
+        }
+        pos = data_start + data_len;
+    }
+    None
+}
+
+// DNS 이름 디코딩 (압축 포인터 지원). 이름과 이름 다음 위치를 반환
+fn read_name(packet: &[u8], start: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut pos = start;
+    let mut end = None;
+    // 포인터 순환 방지
+    for _ in 0..128 {
+        let len = *packet.get(pos)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let offset = ((len & 0x3F) << 8) | *packet.get(pos + 1)? as usize;
+            end.get_or_insert(pos + 2);
+            pos = offset;
+            continue;
+        }
+        let label = packet.get(pos + 1..pos + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        pos += 1 + len;
+    }
+    None
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rocket::futures::future::join_all;
+use sqlx::SqlitePool;
+use crate::config::MonitorConfig;
+use crate::db::device::{Device, DeviceOperations, DeviceStatus};
+use crate::db::probe::{Probe, ProbeOperations};
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::host::resolve_host;
+use crate::module::probe::{check_http_service, check_tcp_service, expand_probe_url, is_reachable, ServiceCheck};
+
+// 헬스 체크 항목의 확인 주기가 지났는지 검사하는 간격
+const PROBE_TICK: Duration = Duration::from_secs(5);
+
+// 모든 장치의 온라인 여부를 주기적으로 확인하여 DB에 기록하는 백그라운드 작업 시작
+// 장치별 헬스 체크는 상태 모니터링 사용 여부(enabled)와 관계없이 실행
+pub fn start_monitor(pool: SqlitePool, config: MonitorConfig, events: EventBus) {
+    let probe_pool = pool.clone();
+    let probe_events = events.clone();
+    rocket::tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let running = Arc::new(Mutex::new(HashSet::new()));
+        loop {
+            if let Err(e) = run_due_probes(&probe_pool, &client, &probe_events, &running).await {
+                eprintln!("Health probe failed: {}", e);
+            }
+            rocket::tokio::time::sleep(PROBE_TICK).await;
+        }
+    });
+
+    if !config.enabled {
+        return;
+    }
+    println!("Device monitor started. (interval: {}s)", config.interval_secs);
+    rocket::tokio::spawn(async move {
+        let interval = Duration::from_secs(config.interval_secs.max(1));
+        loop {
+            if let Err(e) = check_all(&pool, &config, &events).await {
+                eprintln!("Device monitor failed: {}", e);
+            }
+            rocket::tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+async fn check_all(pool: &SqlitePool, config: &MonitorConfig, events: &EventBus) -> Result<(), sqlx::Error> {
+    let devices = Device::get_all(pool).await?;
+    let checks = devices.iter().map(|device| check_device(device, config));
+    let results = join_all(checks).await;
+
+    for (device, status) in devices.iter().zip(results) {
+        record_status(pool, events, device, status).await?;
+    }
+    Ok(())
+}
+
+// 장치 하나의 온라인 여부를 즉시 확인하여 기록 (주기적 확인을 기다리지 않고 갱신할 때 사용)
+pub async fn refresh_device(pool: &SqlitePool, config: &MonitorConfig, events: &EventBus, device: &Device)
+    -> Result<DeviceStatus, sqlx::Error> {
+    let status = check_device(device, config).await;
+    record_status(pool, events, device, status).await?;
+    Ok(status)
+}
+
+async fn record_status(pool: &SqlitePool, events: &EventBus, device: &Device, status: DeviceStatus) -> Result<(), sqlx::Error> {
+    // 온라인인 경우 last_seen 갱신을 위해 매번 기록
+    let changed = device.status != status.as_str();
+    if status == DeviceStatus::Online || changed {
+        Device::update_status(pool, device.id, status).await?;
+    }
+    if changed {
+        events.publish(DeviceEvent::StatusChanged { device_id: device.id, status: status.as_str().to_string() });
+    }
+    Ok(())
+}
+
+async fn check_device(device: &Device, config: &MonitorConfig) -> DeviceStatus {
+    let ip = match resolve_host(&device.ip).await {
+        Some(ip) => ip,
+        None => return DeviceStatus::Unknown,
+    };
+
+    let ports = reachability_ports(device, config);
+    if is_reachable(ip, &ports, Duration::from_millis(config.timeout_ms)).await {
+        DeviceStatus::Online
+    } else {
+        DeviceStatus::Offline
+    }
+}
+
+// 응답 확인에 사용할 TCP 포트 (ICMP를 사용할 수 없거나 응답이 없을 때 확인하는 포트와 장치의 verify_port)
+pub fn reachability_ports(device: &Device, config: &MonitorConfig) -> Vec<u16> {
+    let mut ports = config.tcp_fallback_ports.clone();
+    if let Some(port) = device.verify_port.and_then(|port| u16::try_from(port).ok())
+        && !ports.contains(&port) {
+        ports.push(port);
+    }
+    ports
+}
+
+// 확인 주기가 지난 헬스 체크 항목을 각각 별도 작업으로 실행하고 결과를 기록
+// 느린 항목이 다른 항목의 확인을 늦추지 않도록 기다리지 않으며, 이전 확인이 끝나지 않은 항목(running)은 건너뜀
+async fn run_due_probes(pool: &SqlitePool, client: &reqwest::Client, events: &EventBus, running: &Arc<Mutex<HashSet<i64>>>)
+    -> Result<(), sqlx::Error> {
+    let probes = Probe::get_due(pool).await?;
+    if probes.is_empty() {
+        return Ok(());
+    }
+    let devices = Device::get_all(pool).await?;
+    for probe in probes {
+        if !running.lock().unwrap().insert(probe.id) {
+            continue;
+        }
+        let host = devices.iter()
+            .find(|device| device.id == probe.device_id)
+            .map(|device| device.ip.clone())
+            .unwrap_or_default();
+        let (pool, client, events, running) = (pool.clone(), client.clone(), events.clone(), running.clone());
+        rocket::tokio::spawn(async move {
+            let result = run_probe(&probe, resolve_host(&host).await, &client).await;
+            if let Err(e) = record_probe(&pool, &events, &probe, result).await {
+                eprintln!("Health probe failed: {}", e);
+            }
+            running.lock().unwrap().remove(&probe.id);
+        });
+    }
+    Ok(())
+}
+
+async fn record_probe(pool: &SqlitePool, events: &EventBus, probe: &Probe, result: ServiceCheck) -> Result<(), sqlx::Error> {
+    Probe::update_result(pool, probe.id, result.up, result.up.then_some(result.latency_ms), result.error.as_deref()).await?;
+    let status = if result.up { "up" } else { "down" };
+    if probe.status != status {
+        events.publish(DeviceEvent::ProbeChanged { device_id: probe.device_id, probe_id: probe.id, status: status.to_string() });
+    }
+    Ok(())
+}
+
+async fn run_probe(probe: &Probe, ip: Option<IpAddr>, client: &reqwest::Client) -> ServiceCheck {
+    let wait = Duration::from_millis(probe.timeout_ms.max(1) as u64);
+    let failed = |error: &str| ServiceCheck { up: false, latency_ms: 0, error: Some(error.to_string()) };
+
+    match probe.kind.as_str() {
+        "tcp" => {
+            let Some(ip) = ip else { return failed("Device has no IP address") };
+            let Some(port) = probe.port.and_then(|port| u16::try_from(port).ok()) else { return failed("Invalid port") };
+            check_tcp_service(ip, port, wait).await
+        }
+        "http" => {
+            let url = match expand_probe_url(probe.url.as_deref().unwrap_or_default(), ip) {
+                Ok(url) => url,
+                Err(e) => return failed(&e),
+            };
+            let expected_status = probe.expected_status.and_then(|status| u16::try_from(status).ok());
+            check_http_service(client, &url, expected_status, probe.body_contains.as_deref(), wait).await
+        }
+        kind => failed(&format!("Unknown probe kind: {}", kind)),
+    }
+}
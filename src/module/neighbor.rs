@@ -0,0 +1,193 @@
+use std::net::{IpAddr, SocketAddr};
+use std::process::Command;
+use std::time::Duration;
+use rocket::serde::Serialize;
+use rocket::tokio::net::UdpSocket;
+use rocket::tokio::task::spawn_blocking;
+use crate::error::SystemError;
+use crate::module::magic_packet::{format_mac_address, parse_mac_address};
+use crate::module::probe::ping;
+
+// 이웃(ARP/NDP) 테이블 항목
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct NeighborEntry {
+    pub ip: String,
+    // AA:BB:CC:DD:EE:FF 형식
+    pub mac: String,
+    pub interface: Option<String>,
+}
+
+// IP에 해당하는 MAC 주소를 이웃 테이블에서 조회
+// refresh가 true면 먼저 ping(실패시 UDP 패킷)을 보내 테이블에 항목이 생기도록 함
+pub async fn resolve_mac(ip: IpAddr, refresh: bool, wait: Duration) -> Result<Option<NeighborEntry>, SystemError> {
+    if refresh {
+        populate_neighbor_cache(ip, wait).await;
+    }
+    let entries = neighbor_table(ip.is_ipv6()).await?;
+    Ok(entries.into_iter().find(|entry| entry.ip.parse::<IpAddr>().ok() == Some(ip)))
+}
+
+// 현재 이웃 테이블 전체 조회 (ipv6가 true면 NDP 테이블)
+pub async fn neighbor_table(ipv6: bool) -> Result<Vec<NeighborEntry>, SystemError> {
+    spawn_blocking(move || read_neighbor_table(ipv6)).await
+        .map_err(|e| SystemError::APIError(500, 0, format!("Neighbor lookup task failed: {}", e)))?
+}
+
+// 장치에 패킷을 보내 OS가 이웃 테이블에 항목을 추가하도록 함
+pub async fn populate_neighbor_cache(ip: IpAddr, wait: Duration) {
+    if let Ok(true) = ping(ip, wait).await {
+        return;
+    }
+    // ICMP를 사용할 수 없거나 응답이 없어도 패킷을 보내면 OS가 주소 해석을 시도함
+    let bind_addr: SocketAddr = if ip.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" }.parse().unwrap();
+    if let Ok(socket) = UdpSocket::bind(bind_addr).await {
+        let _ = socket.send_to(&[0], SocketAddr::new(ip, 9)).await;
+        rocket::tokio::time::sleep(wait.min(Duration::from_millis(500))).await;
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_neighbor_table(ipv6: bool) -> Result<Vec<NeighborEntry>, SystemError> {
+    // IPv6 이웃 테이블은 /proc에 없으므로 iproute2 사용
+    if ipv6 {
+        return Ok(parse_ip_neigh(&run_command("ip", &["-6", "neigh", "show"])?));
+    }
+    let content = std::fs::read_to_string("/proc/net/arp")
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to read /proc/net/arp: {}", e)))?;
+    Ok(parse_proc_net_arp(&content))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_neighbor_table(_ipv6: bool) -> Result<Vec<NeighborEntry>, SystemError> {
+    Ok(parse_arp_a(&run_command("arp", &["-a"])?))
+}
+
+fn run_command(program: &str, args: &[&str]) -> Result<String, SystemError> {
+    let output = Command::new(program).args(args).output()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to run {}: {}", program, e)))?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// /proc/net/arp 형식
+// IP address       HW type     Flags       HW address            Mask     Device
+// 192.168.0.10     0x1         0x2         aa:bb:cc:dd:ee:ff     *        eth0
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_net_arp(content: &str) -> Vec<NeighborEntry> {
+    content.lines().skip(1).filter_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 6 || columns[2] == "0x0" {
+            return None;
+        }
+        Some(NeighborEntry {
+            ip: columns[0].to_string(),
+            mac: normalize_mac(columns[3])?,
+            interface: Some(columns[5].to_string()),
+        })
+    }).collect()
+}
+
+// ip neigh 형식
+// fe80::1 dev eth0 lladdr aa:bb:cc:dd:ee:ff router REACHABLE
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_ip_neigh(content: &str) -> Vec<NeighborEntry> {
+    content.lines().filter_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let value_of = |key: &str| columns.iter().position(|c| *c == key).and_then(|i| columns.get(i + 1)).copied();
+        if columns.last().is_some_and(|state| *state == "FAILED" || *state == "INCOMPLETE") {
+            return None;
+        }
+        Some(NeighborEntry {
+            ip: columns.first()?.to_string(),
+            mac: normalize_mac(value_of("lladdr")?)?,
+            interface: value_of("dev").map(str::to_string),
+        })
+    }).collect()
+}
+
+// arp -a 형식 (Windows: "  192.168.0.10    aa-bb-cc-dd-ee-ff     dynamic",
+// macOS/BSD: "? (192.168.0.10) at aa:bb:cc:dd:ee:ff on en0 ifscope [ethernet]")
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_arp_a(content: &str) -> Vec<NeighborEntry> {
+    content.lines().filter_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let ip = columns.iter()
+            .map(|c| c.trim_matches(|ch| ch == '(' || ch == ')'))
+            .find(|c| c.parse::<IpAddr>().is_ok())?;
+        let mac = columns.iter().find_map(|c| normalize_mac(c))?;
+        let interface = columns.iter().position(|c| *c == "on").and_then(|i| columns.get(i + 1)).map(|c| c.to_string());
+        Some(NeighborEntry { ip: ip.to_string(), mac, interface })
+    }).collect()
+}
+
+// 이웃 테이블의 MAC 표기를 AA:BB:CC:DD:EE:FF로 변환 (00:00:00:00:00:00 등 무효값은 None)
+// macOS의 arp -a는 앞자리 0을 생략하므로(a:b:c:d:e:f) 각 그룹을 2자리로 채운 뒤 파싱
+fn normalize_mac(value: &str) -> Option<String> {
+    let parts: Vec<&str> = value.split([':', '-']).collect();
+    if parts.len() != 6 || parts.iter().any(|part| part.is_empty() || part.len() > 2) {
+        return None;
+    }
+    let padded = parts.iter().map(|part| format!("{:0>2}", part)).collect::<Vec<_>>().join(":");
+    let bytes = parse_mac_address(&padded).ok()?;
+    if bytes.iter().all(|b| *b == 0) || bytes.iter().all(|b| *b == 0xff) {
+        return None;
+    }
+    Some(format_mac_address(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(entries: &[NeighborEntry]) -> Vec<(&str, &str, Option<&str>)> {
+        entries.iter().map(|e| (e.ip.as_str(), e.mac.as_str(), e.interface.as_deref())).collect()
+    }
+
+    #[test]
+    fn parses_proc_net_arp() {
+        let content = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.0.10     0x1         0x2         aa:bb:cc:dd:ee:ff     *        eth0
+192.168.0.11     0x1         0x0         00:00:00:00:00:00     *        eth0
+192.168.0.12     0x1         0x2         00:11:22:33:44:55     *        wlan0
+";
+        assert_eq!(summary(&parse_proc_net_arp(content)), vec![
+            ("192.168.0.10", "AA:BB:CC:DD:EE:FF", Some("eth0")),
+            ("192.168.0.12", "00:11:22:33:44:55", Some("wlan0")),
+        ]);
+    }
+
+    #[test]
+    fn parses_ip_neigh() {
+        let content = "\
+fe80::1 dev eth0 lladdr aa:bb:cc:dd:ee:ff router REACHABLE
+fe80::2 dev eth0 lladdr 00:11:22:33:44:55 STALE
+fe80::3 dev eth0 INCOMPLETE
+fe80::4 dev eth0 lladdr 00:11:22:33:44:66 FAILED
+";
+        assert_eq!(summary(&parse_ip_neigh(content)), vec![
+            ("fe80::1", "AA:BB:CC:DD:EE:FF", Some("eth0")),
+            ("fe80::2", "00:11:22:33:44:55", Some("eth0")),
+        ]);
+    }
+
+    #[test]
+    fn parses_windows_arp_a() {
+        let content = "
+Interface: 192.168.0.2 --- 0x4
+  Internet Address      Physical Address      Type
+  192.168.0.10          aa-bb-cc-dd-ee-ff     dynamic
+  192.168.0.255         ff-ff-ff-ff-ff-ff     static
+";
+        assert_eq!(summary(&parse_arp_a(content)), vec![("192.168.0.10", "AA:BB:CC:DD:EE:FF", None)]);
+    }
+
+    #[test]
+    fn parses_macos_arp_a_with_short_groups() {
+        let content = "\
+? (192.168.0.10) at a:b:c:d:e:f on en0 ifscope [ethernet]
+? (192.168.0.11) at (incomplete) on en0 ifscope [ethernet]
+";
+        assert_eq!(summary(&parse_arp_a(content)), vec![("192.168.0.10", "0A:0B:0C:0D:0E:0F", Some("en0"))]);
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+use rocket::serde::Serialize;
+use crate::error::SystemError;
+use crate::module::magic_packet::{format_mac_address, parse_mac_address};
+
+// 저장소의 IEEE MA-L 목록(resources/oui.txt)을 빌드시 정렬/검증하여 생성한 MAC 제조사 목록 (build.rs 참고)
+const EMBEDDED_OUI: &str = include_str!(concat!(env!("OUT_DIR"), "/oui.txt"));
+
+lazy_static! {
+    static ref OUI_TABLE: HashMap<[u8; 3], &'static str> = parse_oui_table(EMBEDDED_OUI);
+}
+
+fn parse_oui_table(content: &'static str) -> HashMap<[u8; 3], &'static str> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (prefix, vendor) = line.split_once('\t')?;
+            let prefix = u32::from_str_radix(prefix.trim(), 16).ok()?;
+            let [_, a, b, c] = prefix.to_be_bytes();
+            Some(([a, b, c], vendor.trim()))
+        })
+        .collect()
+}
+
+// MAC 주소 분석 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct MacInfo {
+    // AA:BB:CC:DD:EE:FF 형식
+    pub mac: String,
+    pub vendor: Option<String>,
+    // 첫 바이트의 U/L 비트 (가상머신 등에서 임의로 지정한 주소)
+    pub locally_administered: bool,
+    // 첫 바이트의 I/G 비트 (장치 주소로 사용할 수 없음)
+    pub multicast: bool,
+}
+
+pub fn mac_info(mac: &str) -> Result<MacInfo, SystemError> {
+    let bytes = parse_mac_address(mac)?;
+    Ok(MacInfo {
+        mac: format_mac_address(&bytes),
+        vendor: vendor_of(&bytes),
+        locally_administered: bytes[0] & 0x02 != 0,
+        multicast: bytes[0] & 0x01 != 0,
+    })
+}
+
+// MAC 주소 앞 3바이트(OUI)로 제조사 조회
+pub fn lookup_vendor(mac: &str) -> Option<String> {
+    vendor_of(&parse_mac_address(mac).ok()?)
+}
+
+fn vendor_of(bytes: &[u8; 6]) -> Option<String> {
+    OUI_TABLE.get(&[bytes[0], bytes[1], bytes[2]]).map(|vendor| vendor.to_string())
+}
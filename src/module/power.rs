@@ -0,0 +1,253 @@
+pub mod wol;
+pub mod redfish;
+pub mod ipmi;
+pub mod smart_plug;
+
+use std::time::Duration;
+use rocket::serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use sqlx::SqlitePool;
+use crate::config::{CryptoConfig, IpmiConfig, MonitorConfig, SshConfig, WolConfig};
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::crypto::{decrypt, encrypt};
+use crate::module::events::DeviceEvent;
+use crate::module::transport::{PacketTransport, SharedTransport};
+use crate::module::wake::WakeReport;
+
+// 드라이버 설정에서 암호화하여 저장하는 항목
+const SECRET_KEYS: [&str; 1] = ["password"];
+// HTTP 기반 드라이버의 기본 요청 타임아웃
+pub const DEFAULT_POWER_TIMEOUT_MS: u64 = 10_000;
+
+// 장치 전원 제어 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerDriverKind {
+    // 매직패킷으로 켜고 SSH 종료 명령 또는 Sleep-on-LAN으로 끔
+    Wol,
+    // BMC의 Redfish REST API (접속 정보는 장치의 bmc_* 항목)
+    Redfish,
+    // ipmitool을 통한 IPMI-over-LAN
+    Ipmi,
+    // Tasmota/Shelly 방식의 HTTP 스마트 플러그
+    SmartPlug,
+}
+
+impl PowerDriverKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "wol" => Some(PowerDriverKind::Wol),
+            "redfish" => Some(PowerDriverKind::Redfish),
+            "ipmi" => Some(PowerDriverKind::Ipmi),
+            "smart_plug" => Some(PowerDriverKind::SmartPlug),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowerDriverKind::Wol => "wol",
+            PowerDriverKind::Redfish => "redfish",
+            PowerDriverKind::Ipmi => "ipmi",
+            PowerDriverKind::SmartPlug => "smart_plug",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum PowerState { On, Off, Unknown }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerOperation { On, Off }
+
+impl PowerOperation {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "on" => Some(PowerOperation::On),
+            "off" => Some(PowerOperation::Off),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowerOperation::On => "on",
+            PowerOperation::Off => "off",
+        }
+    }
+}
+
+// 전원 제어 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct PowerReport {
+    pub device_id: i64,
+    pub driver: String,
+    pub operation: String,
+    // 드라이버가 응답으로 알려준 상태 (알 수 없으면 unknown)
+    pub state: PowerState,
+    // 드라이버별 상세 결과 (웨이크 리포트, SSH 출력, BMC 응답 등)
+    pub detail: Option<Value>,
+}
+
+impl PowerReport {
+    // WoL 드라이버로 켠 경우의 매직패킷 전송 결과
+    pub fn wake_report(&self) -> Option<WakeReport> {
+        if self.driver != PowerDriverKind::Wol.as_str() || self.operation != PowerOperation::On.as_str() {
+            return None;
+        }
+        serde_json::from_value(self.detail.clone()?).ok()
+    }
+
+    // 켜기 요청이 장치에 전달되지 않은 경우의 사유 (매직패킷을 하나도 보내지 못함)
+    pub fn failure(&self) -> Option<String> {
+        self.wake_report()
+            .filter(|report| report.sent == 0)
+            .map(|_| "Failed to send magic packet".to_string())
+    }
+
+    // 결과를 알리는 이벤트 (WoL로 켠 경우는 기존과 같이 매직패킷 전송 결과를 wake 이벤트로 전달)
+    pub fn event(&self) -> DeviceEvent {
+        match self.wake_report() {
+            Some(report) => DeviceEvent::Wake { report },
+            None => DeviceEvent::Power { device_id: self.device_id, operation: self.operation.clone(), state: self.state },
+        }
+    }
+}
+
+// 드라이버 실행에 필요한 공용 자원
+pub struct PowerContext<'a> {
+    pub pool: &'a SqlitePool,
+    pub transport: &'a dyn PacketTransport,
+    pub crypto: &'a CryptoConfig,
+    pub wol: &'a WolConfig,
+    pub ssh: &'a SshConfig,
+    pub monitor: &'a MonitorConfig,
+    pub ipmi: &'a IpmiConfig,
+}
+
+// 백그라운드 작업에서 사용하는 PowerContext의 소유 버전
+#[derive(Clone)]
+pub struct PowerResources {
+    pub pool: SqlitePool,
+    pub transport: SharedTransport,
+    pub crypto: CryptoConfig,
+    pub wol: WolConfig,
+    pub ssh: SshConfig,
+    pub monitor: MonitorConfig,
+    pub ipmi: IpmiConfig,
+}
+
+impl PowerResources {
+    pub fn context(&self) -> PowerContext<'_> {
+        PowerContext {
+            pool: &self.pool,
+            transport: self.transport.as_ref(),
+            crypto: &self.crypto,
+            wol: &self.wol,
+            ssh: &self.ssh,
+            monitor: &self.monitor,
+            ipmi: &self.ipmi,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait PowerDriver: Send + Sync {
+    async fn power_on(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError>;
+    async fn power_off(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError>;
+    async fn status(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerState, SystemError>;
+}
+
+// 장치에 지정된 드라이버를 설정과 함께 생성 (비밀 값은 복호화됨)
+pub fn driver_for(crypto: &CryptoConfig, device: &Device) -> Result<Box<dyn PowerDriver>, SystemError> {
+    let kind = PowerDriverKind::parse(&device.power_driver)
+        .ok_or(SystemError::APIError(400, 0, format!("Unknown power driver: {}", device.power_driver)))?;
+    let config = open_config(crypto, device.power_config.as_deref())?;
+    Ok(match kind {
+        PowerDriverKind::Wol => Box::new(wol::WolDriver),
+        PowerDriverKind::Redfish => Box::new(redfish::RedfishDriver::new(
+            redfish::RedfishConfig::from_device(crypto, device, parse_config(kind, config)?)?)),
+        PowerDriverKind::Ipmi => Box::new(ipmi::IpmiDriver::new(parse_config(kind, config)?)),
+        PowerDriverKind::SmartPlug => Box::new(smart_plug::SmartPlugDriver::new(parse_config(kind, config)?)),
+    })
+}
+
+fn parse_config<T: DeserializeOwned>(kind: PowerDriverKind, config: Value) -> Result<T, SystemError> {
+    serde_json::from_value(config)
+        .map_err(|e| SystemError::APIError(400, 0, format!("Invalid {} power config: {}", kind.as_str(), e)))
+}
+
+// 저장된 설정 JSON의 비밀 값을 복호화
+fn open_config(crypto: &CryptoConfig, stored: Option<&str>) -> Result<Value, SystemError> {
+    let mut config = match stored {
+        Some(stored) => serde_json::from_str::<Map<String, Value>>(stored)
+            .map_err(|_| SystemError::APIError(500, 0, "Invalid stored power config".to_string()))?,
+        None => Map::new(),
+    };
+    for key in SECRET_KEYS {
+        if let Some(Value::String(encrypted)) = config.get(key) {
+            let plain = decrypt(crypto, encrypted)?;
+            config.insert(key.to_string(), Value::String(plain));
+        }
+    }
+    Ok(Value::Object(config))
+}
+
+// 요청의 설정 JSON을 저장 형식으로 변환
+// 비밀 값은 암호화하며, 요청에 없거나 null이면 기존 값 유지, 빈 문자열이면 제거
+pub fn seal_config(crypto: &CryptoConfig, config: &Value, stored: Option<&str>) -> Result<Option<String>, SystemError> {
+    let mut config = match config {
+        Value::Object(config) => config.clone(),
+        Value::Null => return Ok(None),
+        _ => return Err(SystemError::APIError(400, 0, "Power config must be a JSON object".to_string())),
+    };
+    let previous: Map<String, Value> = stored
+        .and_then(|stored| serde_json::from_str(stored).ok())
+        .unwrap_or_default();
+    for key in SECRET_KEYS {
+        match config.remove(key) {
+            Some(Value::String(plain)) if plain.is_empty() => {}
+            Some(Value::String(plain)) => {
+                config.insert(key.to_string(), Value::String(encrypt(crypto, &plain)?));
+            }
+            Some(Value::Null) | None => {
+                if let Some(old) = previous.get(key) {
+                    config.insert(key.to_string(), old.clone());
+                }
+            }
+            Some(_) => return Err(SystemError::APIError(400, 0, format!("Power config {} must be a string", key))),
+        }
+    }
+    Ok(Some(Value::Object(config).to_string()))
+}
+
+// 응답용 설정 JSON (비밀 값은 제외하고 포함 여부만 반환)
+pub fn public_config(stored: Option<&str>) -> (Option<Value>, bool) {
+    let Some(mut config) = stored.and_then(|stored| serde_json::from_str::<Map<String, Value>>(stored).ok()) else {
+        return (None, false);
+    };
+    let mut has_secret = false;
+    for key in SECRET_KEYS {
+        has_secret |= config.remove(key).is_some();
+    }
+    (Some(Value::Object(config)), has_secret)
+}
+
+// HTTP 기반 드라이버용 클라이언트 (BMC 등 자체 서명 인증서를 쓰는 장비는 insecure 지정)
+pub(crate) fn http_client(insecure: bool, timeout_ms: u64) -> Result<reqwest::Client, SystemError> {
+    reqwest::Client::builder()
+        .danger_accept_invalid_certs(insecure)
+        .timeout(Duration::from_millis(timeout_ms.max(1)))
+        .build()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to create HTTP client: {}", e)))
+}
+
+pub(crate) fn request_error(target: &str, e: reqwest::Error) -> SystemError {
+    SystemError::APIError(502, 0, format!("{} request failed: {}", target, e))
+}
+
+fn default_power_timeout_ms() -> u64 {
+    DEFAULT_POWER_TIMEOUT_MS
+}
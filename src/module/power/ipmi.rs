@@ -0,0 +1,103 @@
+use std::process::{Command, Stdio};
+use rocket::serde::Deserialize;
+use rocket::tokio::task::spawn_blocking;
+use serde_json::json;
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::power::{default_power_timeout_ms, PowerContext, PowerDriver, PowerReport, PowerState};
+
+const DEFAULT_IPMI_PORT: u16 = 623;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct IpmiOptions {
+    // BMC 주소
+    pub host: String,
+    #[serde(default = "default_ipmi_port")]
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    // 끄기시 ACPI 종료(soft) 대신 강제 종료(off) 사용
+    #[serde(default)]
+    pub force_off: bool,
+    #[serde(default = "default_power_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_ipmi_port() -> u16 {
+    DEFAULT_IPMI_PORT
+}
+
+// ipmitool(lanplus 인터페이스)로 chassis power 명령 실행
+// 실행 파일은 장치 설정이 아닌 Rocket.toml의 [ipmi] ipmitool을 사용
+// 로컬 테스트는 OpenIPMI의 ipmi_sim 등 IPMI 시뮬레이터의 주소/포트를 지정
+pub struct IpmiDriver {
+    config: IpmiOptions,
+}
+
+impl IpmiDriver {
+    pub fn new(config: IpmiOptions) -> Self {
+        Self { config }
+    }
+
+    async fn chassis_power(&self, ctx: &PowerContext<'_>, command: &str) -> Result<String, SystemError> {
+        let ipmitool = ctx.ipmi.ipmitool.clone();
+        let config = self.config.clone();
+        let command = command.to_string();
+        spawn_blocking(move || run_ipmitool(&ipmitool, &config, &command)).await
+            .map_err(|e| SystemError::APIError(500, 0, format!("IPMI task failed: {}", e)))?
+    }
+
+    async fn run(&self, ctx: &PowerContext<'_>, device: &Device, command: &str, operation: &str) -> Result<PowerReport, SystemError> {
+        let output = self.chassis_power(ctx, command).await?;
+        Ok(PowerReport {
+            device_id: device.id,
+            driver: "ipmi".to_string(),
+            operation: operation.to_string(),
+            state: PowerState::Unknown,
+            detail: Some(json!({ "command": command, "output": output })),
+        })
+    }
+}
+
+// 비밀번호는 프로세스 목록에 노출되지 않도록 환경 변수(-E)로 전달
+// 응답 대기 시간은 ipmitool의 -N(초 단위)과 재시도 1회(-R)로 제한
+fn run_ipmitool(ipmitool: &str, config: &IpmiOptions, command: &str) -> Result<String, SystemError> {
+    let wait_secs = config.timeout_ms.div_ceil(1000).max(1).to_string();
+    let output = Command::new(ipmitool)
+        .args(["-I", "lanplus", "-H", &config.host, "-p", &config.port.to_string(), "-U", &config.username, "-E",
+            "-N", &wait_secs, "-R", "1", "chassis", "power", command])
+        .env("IPMI_PASSWORD", &config.password)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to run {}: {}", ipmitool, e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(SystemError::APIError(502, 0, format!("IPMI chassis power {} failed: {}", command, stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[async_trait::async_trait]
+impl PowerDriver for IpmiDriver {
+    async fn power_on(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.run(ctx, device, "on", "on").await
+    }
+
+    async fn power_off(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.run(ctx, device, if self.config.force_off { "off" } else { "soft" }, "off").await
+    }
+
+    // "Chassis Power is on" 형식의 출력
+    async fn status(&self, ctx: &PowerContext<'_>, _device: &Device) -> Result<PowerState, SystemError> {
+        let output = self.chassis_power(ctx, "status").await?.to_lowercase();
+        Ok(if output.ends_with(" on") {
+            PowerState::On
+        } else if output.ends_with(" off") {
+            PowerState::Off
+        } else {
+            PowerState::Unknown
+        })
+    }
+}
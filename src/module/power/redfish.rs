@@ -0,0 +1,147 @@
+use rocket::serde::Deserialize;
+use serde_json::{json, Value};
+use crate::config::CryptoConfig;
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::crypto::decrypt;
+use crate::module::power::{default_power_timeout_ms, http_client, request_error, PowerContext, PowerDriver, PowerReport,
+                           PowerState};
+
+const SYSTEMS_PATH: &str = "/redfish/v1/Systems";
+
+// 끄기에 사용할 수 있는 ResetType
+const OFF_RESET_TYPES: [&str; 3] = ["GracefulShutdown", "ForceOff", "PushPowerButton"];
+
+// power_config에 저장하는 Redfish 드라이버 설정 (접속 정보는 장치의 bmc_* 항목)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct RedfishOptions {
+    // 끄기에 사용하는 ResetType (기본값은 OS의 정상 종료 절차를 거치는 GracefulShutdown)
+    #[serde(default = "default_off_reset_type")]
+    pub off_reset_type: String,
+    #[serde(default = "default_power_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_off_reset_type() -> String {
+    OFF_RESET_TYPES[0].to_string()
+}
+
+// 장치에 저장된 BMC 접속 정보
+#[derive(Debug, Clone)]
+pub struct RedfishConfig {
+    // BMC 주소 (예: https://10.0.0.5)
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    // 미지정시 /redfish/v1/Systems의 첫 번째 시스템
+    pub system_id: Option<String>,
+    // 자체 서명 인증서 허용
+    pub insecure: bool,
+    pub off_reset_type: String,
+    pub timeout_ms: u64,
+}
+
+impl RedfishConfig {
+    pub fn from_device(crypto: &CryptoConfig, device: &Device, options: RedfishOptions) -> Result<Self, SystemError> {
+        let missing = |field: &str| SystemError::APIError(400, 0, format!("Redfish driver requires {}", field));
+        if !OFF_RESET_TYPES.contains(&options.off_reset_type.as_str()) {
+            return Err(SystemError::APIError(400, 0,
+                format!("Redfish off_reset_type must be one of {}", OFF_RESET_TYPES.join(", "))));
+        }
+        Ok(Self {
+            url: device.bmc_url.clone().ok_or(missing("a BMC URL"))?,
+            username: device.bmc_user.clone().ok_or(missing("a BMC user"))?,
+            password: match &device.bmc_password {
+                Some(encrypted) => decrypt(crypto, encrypted)?,
+                None => String::new(),
+            },
+            system_id: device.bmc_system_id.clone(),
+            insecure: device.bmc_insecure,
+            off_reset_type: options.off_reset_type,
+            timeout_ms: options.timeout_ms,
+        })
+    }
+}
+
+pub struct RedfishDriver {
+    config: RedfishConfig,
+}
+
+impl RedfishDriver {
+    pub fn new(config: RedfishConfig) -> Self {
+        Self { config }
+    }
+
+    fn base_url(&self) -> &str {
+        self.config.url.trim_end_matches('/')
+    }
+
+    async fn get(&self, client: &reqwest::Client, path: &str) -> Result<Value, SystemError> {
+        let res = client.get(format!("{}{}", self.base_url(), path))
+            .basic_auth(&self.config.username, Some(&self.config.password))
+            .send().await
+            .map_err(|e| request_error("Redfish", e))?;
+        let status = res.status();
+        if !status.is_success() {
+            return Err(SystemError::APIError(502, 0, format!("Redfish GET {} returned {}", path, status.as_u16())));
+        }
+        res.json().await.map_err(|e| request_error("Redfish", e))
+    }
+
+    // 제어 대상 시스템의 리소스 경로 (/redfish/v1/Systems/<id>)
+    async fn system_path(&self, client: &reqwest::Client) -> Result<String, SystemError> {
+        if let Some(system_id) = self.config.system_id.as_deref().filter(|id| !id.is_empty()) {
+            return Ok(format!("{}/{}", SYSTEMS_PATH, system_id));
+        }
+        let systems = self.get(client, SYSTEMS_PATH).await?;
+        systems["Members"][0]["@odata.id"].as_str()
+            .map(str::to_string)
+            .ok_or(SystemError::APIError(502, 0, "Redfish service has no computer systems".to_string()))
+    }
+
+    async fn reset(&self, device: &Device, reset_type: &str, operation: &str) -> Result<PowerReport, SystemError> {
+        let client = http_client(self.config.insecure, self.config.timeout_ms)?;
+        let system = self.system_path(&client).await?;
+        let path = format!("{}/Actions/ComputerSystem.Reset", system);
+        let res = client.post(format!("{}{}", self.base_url(), path))
+            .basic_auth(&self.config.username, Some(&self.config.password))
+            .json(&json!({ "ResetType": reset_type }))
+            .send().await
+            .map_err(|e| request_error("Redfish", e))?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            return Err(SystemError::APIError(502, 0,
+                format!("Redfish reset ({}) returned {}: {}", reset_type, status.as_u16(), body.trim())));
+        }
+        Ok(PowerReport {
+            device_id: device.id,
+            driver: "redfish".to_string(),
+            operation: operation.to_string(),
+            state: PowerState::Unknown,
+            detail: Some(json!({ "system": system, "reset_type": reset_type, "status": status.as_u16() })),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PowerDriver for RedfishDriver {
+    async fn power_on(&self, _ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.reset(device, "On", "on").await
+    }
+
+    async fn power_off(&self, _ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.reset(device, &self.config.off_reset_type, "off").await
+    }
+
+    async fn status(&self, _ctx: &PowerContext<'_>, _device: &Device) -> Result<PowerState, SystemError> {
+        let client = http_client(self.config.insecure, self.config.timeout_ms)?;
+        let system = self.get(&client, &self.system_path(&client).await?).await?;
+        Ok(match system["PowerState"].as_str() {
+            Some("On") | Some("PoweringOff") => PowerState::On,
+            Some("Off") | Some("PoweringOn") => PowerState::Off,
+            _ => PowerState::Unknown,
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use rocket::serde::Deserialize;
+use serde_json::Value;
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::power::{default_power_timeout_ms, http_client, request_error, PowerContext, PowerDriver, PowerReport, PowerState};
+
+// 스마트 플러그 HTTP API 종류
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum PlugKind {
+    // GET /cm?cmnd=Power%20On -> {"POWER":"ON"}
+    Tasmota,
+    // Shelly Gen1: GET /relay/0?turn=on -> {"ison":true}
+    Shelly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct SmartPlugConfig {
+    pub kind: PlugKind,
+    // 플러그 주소 (예: http://192.168.0.50)
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    // 여러 채널을 가진 플러그의 채널 번호 (Tasmota는 1부터, Shelly는 0부터. 미지정시 기본 채널)
+    #[serde(default)]
+    pub relay: Option<u8>,
+    #[serde(default = "default_power_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+// 플러그 전원을 직접 켜고 끄는 드라이버 (끄기는 장치의 정상 종료 없이 전원을 차단함)
+pub struct SmartPlugDriver {
+    config: SmartPlugConfig,
+}
+
+impl SmartPlugDriver {
+    pub fn new(config: SmartPlugConfig) -> Self {
+        Self { config }
+    }
+
+    // turn: Some("on"/"off")이면 전환, None이면 상태 조회
+    async fn request(&self, turn: Option<&str>) -> Result<Value, SystemError> {
+        let base = self.config.url.trim_end_matches('/');
+        let client = http_client(false, self.config.timeout_ms)?;
+        let request = match self.config.kind {
+            PlugKind::Tasmota => {
+                let power = match self.config.relay {
+                    Some(relay) => format!("Power{}", relay),
+                    None => "Power".to_string(),
+                };
+                let command = match turn {
+                    Some(turn) => format!("{} {}", power, turn),
+                    None => power,
+                };
+                let mut query = vec![("cmnd", command)];
+                if let Some(username) = &self.config.username {
+                    query.push(("user", username.clone()));
+                }
+                if let Some(password) = &self.config.password {
+                    query.push(("password", password.clone()));
+                }
+                client.get(format!("{}/cm", base)).query(&query)
+            }
+            PlugKind::Shelly => {
+                let request = client.get(format!("{}/relay/{}", base, self.config.relay.unwrap_or(0)));
+                let request = match turn {
+                    Some(turn) => request.query(&[("turn", turn)]),
+                    None => request,
+                };
+                match &self.config.username {
+                    Some(username) => request.basic_auth(username, self.config.password.as_ref()),
+                    None => request,
+                }
+            }
+        };
+
+        let res = request.send().await.map_err(|e| request_error("Smart plug", e))?;
+        let status = res.status();
+        if !status.is_success() {
+            return Err(SystemError::APIError(502, 0, format!("Smart plug returned {}", status.as_u16())));
+        }
+        res.json().await.map_err(|e| request_error("Smart plug", e))
+    }
+
+    fn parse_state(&self, body: &Value) -> PowerState {
+        let on = match self.config.kind {
+            // 채널 번호에 따라 POWER 또는 POWER1, POWER2 ... 키로 응답
+            PlugKind::Tasmota => body.as_object()
+                .and_then(|body| body.iter().find(|(key, _)| key.starts_with("POWER")))
+                .and_then(|(_, value)| value.as_str())
+                .map(|value| value.eq_ignore_ascii_case("on")),
+            PlugKind::Shelly => body["ison"].as_bool(),
+        };
+        match on {
+            Some(true) => PowerState::On,
+            Some(false) => PowerState::Off,
+            None => PowerState::Unknown,
+        }
+    }
+
+    async fn switch(&self, device: &Device, turn: &str) -> Result<PowerReport, SystemError> {
+        let body = self.request(Some(turn)).await?;
+        Ok(PowerReport {
+            device_id: device.id,
+            driver: "smart_plug".to_string(),
+            operation: turn.to_string(),
+            state: self.parse_state(&body),
+            detail: Some(body),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PowerDriver for SmartPlugDriver {
+    async fn power_on(&self, _ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.switch(device, "on").await
+    }
+
+    async fn power_off(&self, _ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        self.switch(device, "off").await
+    }
+
+    async fn status(&self, _ctx: &PowerContext<'_>, _device: &Device) -> Result<PowerState, SystemError> {
+        let body = self.request(None).await?;
+        Ok(self.parse_state(&body))
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::host::resolve_host;
+use crate::module::monitor::reachability_ports;
+use crate::module::power::{PowerContext, PowerDriver, PowerReport, PowerState};
+use crate::module::probe::is_reachable;
+use crate::module::ssh;
+use crate::module::wake::{sleep, wake};
+
+// 기존 웨이크 설정을 사용하는 기본 드라이버
+// 끄기는 SSH 계정이 설정되어 있으면 종료 명령, 아니면 Sleep-on-LAN 패킷으로 처리
+pub struct WolDriver;
+
+#[async_trait::async_trait]
+impl PowerDriver for WolDriver {
+    async fn power_on(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        let report = wake(ctx.transport, ctx.crypto, ctx.wol, device).await?;
+        Ok(PowerReport {
+            device_id: device.id,
+            driver: "wol".to_string(),
+            operation: "on".to_string(),
+            // 매직패킷은 응답이 없으므로 켜졌는지 알 수 없음
+            state: PowerState::Unknown,
+            detail: serde_json::to_value(report).ok(),
+        })
+    }
+
+    async fn power_off(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerReport, SystemError> {
+        let detail = if device.ssh_user.is_some() {
+            serde_json::to_value(ssh::power_off(ctx.pool, ctx.crypto, ctx.ssh, device).await?)
+        } else if device.sleep_on_lan {
+            serde_json::to_value(sleep(ctx.transport, ctx.wol, device).await?)
+        } else {
+            return Err(SystemError::APIError(400, 0,
+                "WoL driver requires SSH credentials or Sleep-on-LAN to power off".to_string()));
+        };
+        Ok(PowerReport {
+            device_id: device.id,
+            driver: "wol".to_string(),
+            operation: "off".to_string(),
+            state: PowerState::Unknown,
+            detail: detail.ok(),
+        })
+    }
+
+    // 상태 모니터링과 같은 방식(ICMP, TCP 포트)으로 응답 여부 확인
+    async fn status(&self, ctx: &PowerContext<'_>, device: &Device) -> Result<PowerState, SystemError> {
+        let Some(ip) = resolve_host(&device.ip).await else {
+            return Ok(PowerState::Unknown);
+        };
+        let ports = reachability_ports(device, ctx.monitor);
+        Ok(if is_reachable(ip, &ports, Duration::from_millis(ctx.monitor.timeout_ms)).await {
+            PowerState::On
+        } else {
+            PowerState::Off
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use rand_core::{OsRng, RngCore};
+use rocket::futures::future::join_all;
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::time::timeout;
+use surge_ping::{Client, Config, PingIdentifier, PingSequence, ICMP};
+use crate::error::SystemError;
+
+// ICMP echo 요청으로 응답 여부 확인
+// ICMP 소켓을 열 수 없는 경우(권한 부족 등) Err를 반환하므로 호출측에서 TCP 확인으로 대체해야 함
+pub async fn ping(ip: IpAddr, wait: Duration) -> Result<bool, SystemError> {
+    let config = match ip {
+        IpAddr::V4(_) => Config::default(),
+        IpAddr::V6(_) => Config::builder().kind(ICMP::V6).build(),
+    };
+    let client = Client::new(&config)
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to open ICMP socket: {}", e)))?;
+
+    let mut pinger = client.pinger(ip, PingIdentifier(OsRng.next_u32() as u16)).await;
+    pinger.timeout(wait);
+    Ok(pinger.ping(PingSequence(0), &[0; 16]).await.is_ok())
+}
+
+// TCP 연결 시도로 호스트가 살아있는지 확인
+// 연결이 거부(RST)되어도 호스트 자체는 응답한 것이므로 살아있는 것으로 판단
+pub async fn tcp_alive(ip: IpAddr, port: u16, wait: Duration) -> bool {
+    match timeout(wait, TcpStream::connect(SocketAddr::new(ip, port))).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => e.kind() == ErrorKind::ConnectionRefused,
+        Err(_) => false,
+    }
+}
+
+// ICMP 확인 후 응답이 없거나 ICMP를 사용할 수 없으면 지정된 TCP 포트들로 동시에 확인
+pub async fn is_reachable(ip: IpAddr, tcp_ports: &[u16], wait: Duration) -> bool {
+    if let Ok(true) = ping(ip, wait).await {
+        return true;
+    }
+    let checks = tcp_ports.iter().map(|port| tcp_alive(ip, *port, wait));
+    join_all(checks).await.into_iter().any(|alive| alive)
+}
+
+// 서비스 헬스 체크 결과
+pub struct ServiceCheck {
+    pub up: bool,
+    pub latency_ms: i64,
+    pub error: Option<String>,
+}
+
+impl ServiceCheck {
+    fn new(started: Instant, result: Result<(), String>) -> Self {
+        Self {
+            up: result.is_ok(),
+            latency_ms: started.elapsed().as_millis() as i64,
+            error: result.err(),
+        }
+    }
+}
+
+// 서비스 포트에 TCP 연결이 되는지 확인 (호스트 생존 확인과 달리 연결 거부는 실패로 판단)
+pub async fn check_tcp_service(ip: IpAddr, port: u16, wait: Duration) -> ServiceCheck {
+    let started = Instant::now();
+    let result = match timeout(wait, TcpStream::connect(SocketAddr::new(ip, port))).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(format!("Connection failed: {}", e)),
+        Err(_) => Err("Timed out".to_string()),
+    };
+    ServiceCheck::new(started, result)
+}
+
+// HTTP GET 요청 후 상태 코드와 응답 본문 확인
+// 기대 상태 코드가 없으면 2xx를 성공으로 판단
+pub async fn check_http_service(client: &reqwest::Client, url: &str, expected_status: Option<u16>,
+                                body_contains: Option<&str>, wait: Duration) -> ServiceCheck {
+    let started = Instant::now();
+    let result = async {
+        let res = client.get(url).timeout(wait).send().await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        let status = res.status();
+        let status_ok = match expected_status {
+            Some(expected) => status.as_u16() == expected,
+            None => status.is_success(),
+        };
+        if !status_ok {
+            return Err(format!("Unexpected status: {}", status.as_u16()));
+        }
+        if let Some(expected) = body_contains {
+            let body = res.text().await.map_err(|e| format!("Failed to read body: {}", e))?;
+            if !body.contains(expected) {
+                return Err("Response body does not contain expected text".to_string());
+            }
+        }
+        Ok(())
+    }.await;
+    ServiceCheck::new(started, result)
+}
+
+// URL의 {ip}를 장치 IP로 치환 (IPv6는 대괄호로 감쌈)
+pub fn expand_probe_url(url: &str, ip: Option<IpAddr>) -> Result<String, String> {
+    if !url.contains("{ip}") {
+        return Ok(url.to_string());
+    }
+    match ip {
+        Some(IpAddr::V4(ip)) => Ok(url.replace("{ip}", &ip.to_string())),
+        Some(IpAddr::V6(ip)) => Ok(url.replace("{ip}", &format!("[{}]", ip))),
+        None => Err("Device has no IP address for {ip}".to_string()),
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::Duration;
+use serde_json::json;
+use crate::db::action::{DeviceAction, DeviceActionOperations};
+use crate::db::device::{Device, DeviceOperations};
+use crate::db::scene::{Scene, SceneRun, SceneRunLog, SceneRunLogOperations, SceneRunOperations, SceneStep};
+use crate::error::SystemError;
+use crate::module::action::run_and_log_action;
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::host::resolve_host;
+use crate::module::monitor::reachability_ports;
+use crate::module::power::{driver_for, PowerResources};
+use crate::module::wake_plan::{wait_until_online, wake_with_dependencies};
+
+// 알림 웹훅 요청 타임아웃
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 씬 단계 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneStepKind {
+    // 의존 장치를 먼저 켜서 응답을 확인한 뒤 장치의 전원 드라이버로 켬 (/device/wake/<id>와 동일)
+    // 켜지 못했거나 제한 시간 안에 응답하지 않은 의존 장치가 있으면 실패
+    Wake,
+    // seconds 동안 대기
+    Wait,
+    // 장치가 응답할 때까지 대기 (최대 seconds, 미지정시 verify_timeout_secs, 상태 모니터링과 같은 포트로 확인)
+    WaitOnline,
+    // 장치의 HTTP 동작 실행
+    RunAction,
+    // 장치의 전원 드라이버로 끄기
+    Shutdown,
+    // 이벤트(및 지정시 웹훅)로 알림 전송
+    Notify,
+}
+
+impl SceneStepKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "wake" => Some(SceneStepKind::Wake),
+            "wait" => Some(SceneStepKind::Wait),
+            "wait_online" => Some(SceneStepKind::WaitOnline),
+            "run_action" => Some(SceneStepKind::RunAction),
+            "shutdown" => Some(SceneStepKind::Shutdown),
+            "notify" => Some(SceneStepKind::Notify),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SceneStepKind::Wake => "wake",
+            SceneStepKind::Wait => "wait",
+            SceneStepKind::WaitOnline => "wait_online",
+            SceneStepKind::RunAction => "run_action",
+            SceneStepKind::Shutdown => "shutdown",
+            SceneStepKind::Notify => "notify",
+        }
+    }
+
+    // 대상 장치가 필요한 단계인지 여부
+    pub fn requires_device(self) -> bool {
+        !matches!(self, SceneStepKind::Wait | SceneStepKind::Notify)
+    }
+}
+
+// 씬의 단계를 순서대로 실행하는 작업을 백그라운드로 시작
+// 단계가 실패하면 이후 단계는 실행하지 않으며, 단계별 결과는 scene_run_log에 기록
+pub fn start_scene_run(resources: PowerResources, events: EventBus, scene: Scene, steps: Vec<SceneStep>, run: SceneRun) {
+    rocket::tokio::spawn(async move {
+        events.publish(DeviceEvent::SceneRun { scene_id: scene.id, run_id: run.id, status: run.status.clone() });
+
+        let mut error = None;
+        for step in &steps {
+            let result = run_step(&resources, &events, &scene, run.id, step).await;
+            let (success, message) = match result {
+                Ok(message) => (true, message),
+                Err(e) => (false, Some(e.message())),
+            };
+            let log = SceneRunLog {
+                run_id: run.id,
+                step_order: step.step_order,
+                kind: step.kind.clone(),
+                device_id: step.device_id,
+                success,
+                message: message.clone(),
+                ..Default::default()
+            };
+            if let Err(e) = SceneRunLog::insert(&resources.pool, &log).await {
+                eprintln!("Failed to record scene run log: {}", e);
+            }
+            if !success {
+                error = Some(format!("Step {} ({}) failed: {}", step.step_order + 1, step.kind, message.unwrap_or_default()));
+                break;
+            }
+        }
+
+        let status = if error.is_none() { "succeeded" } else { "failed" };
+        if let Err(e) = SceneRun::finish(&resources.pool, run.id, status, error.as_deref()).await {
+            eprintln!("Failed to record scene run result: {}", e);
+        }
+        events.publish(DeviceEvent::SceneRun { scene_id: scene.id, run_id: run.id, status: status.to_string() });
+    });
+}
+
+// 단계 하나를 실행하고 기록할 결과 메시지를 반환
+async fn run_step(resources: &PowerResources, events: &EventBus, scene: &Scene, run_id: i64, step: &SceneStep)
+    -> Result<Option<String>, SystemError> {
+    let kind = SceneStepKind::parse(&step.kind)
+        .ok_or(SystemError::APIError(400, 0, format!("Unknown scene step: {}", step.kind)))?;
+    let device = match step.device_id {
+        Some(device_id) if kind.requires_device() => Some(Device::get(&resources.pool, device_id).await?
+            .ok_or(SystemError::APIError(404, 0, format!("Device not found: {}", device_id)))?),
+        _ => None,
+    };
+
+    match (kind, device) {
+        (SceneStepKind::Wake, Some(device)) => {
+            let report = wake_with_dependencies(&resources.context(), &device).await?;
+            for step in &report.steps {
+                if let Some(report) = &step.report {
+                    events.publish(report.event());
+                }
+            }
+            let failed = report.steps.iter().find(|step| step.status.blocks_dependents());
+            match failed {
+                Some(step) if step.dependency => Err(SystemError::APIError(502, 0,
+                    format!("{}: {}", device.name, step.blocked_reason()))),
+                Some(step) => Err(SystemError::APIError(502, 0,
+                    format!("{}: {}", step.name, step.error.clone().unwrap_or_default()))),
+                None => Ok(Some(format!("Woke {} device(s)", report.plan.len()))),
+            }
+        }
+        (SceneStepKind::Wait, _) => {
+            let seconds = step.seconds.unwrap_or_default().max(0) as u64;
+            rocket::tokio::time::sleep(Duration::from_secs(seconds)).await;
+            Ok(Some(format!("Waited {}s", seconds)))
+        }
+        (SceneStepKind::WaitOnline, Some(device)) => {
+            let ip = resolve_host(&device.ip).await
+                .ok_or(SystemError::APIError(400, 0, format!("Could not resolve host: {}", device.ip)))?;
+            let ports = reachability_ports(&device, &resources.monitor);
+            let seconds = step.seconds.map(|s| s.max(0) as u64).unwrap_or(resources.wol.verify_timeout_secs);
+            if wait_until_online(ip, &ports, &resources.wol, Duration::from_secs(seconds)).await {
+                Ok(Some(format!("{} is online", device.name)))
+            } else {
+                Err(SystemError::APIError(504, 0, format!("{} did not come up within {}s", device.name, seconds)))
+            }
+        }
+        (SceneStepKind::RunAction, Some(device)) => {
+            let name = step.action_name.as_deref().unwrap_or_default();
+            let action = DeviceAction::get_by_name(&resources.pool, device.id, name).await?
+                .ok_or(SystemError::APIError(404, 0, format!("Action not found: {}", name)))?;
+            let result = run_and_log_action(&resources.pool, &resources.crypto, &device, &action).await?;
+            events.publish(DeviceEvent::ActionRun { device_id: device.id, action: action.name, success: result.success });
+            match result.error {
+                Some(error) if !result.success => Err(SystemError::APIError(502, 0, error)),
+                _ => Ok(result.status_code.map(|status| format!("HTTP {}", status))),
+            }
+        }
+        (SceneStepKind::Shutdown, Some(device)) => {
+            let report = driver_for(&resources.crypto, &device)?.power_off(&resources.context(), &device).await?;
+            events.publish(DeviceEvent::Power { device_id: device.id, operation: report.operation, state: report.state });
+            Ok(Some(format!("Powered off {} via {}", device.name, report.driver)))
+        }
+        (SceneStepKind::Notify, _) => {
+            let message = step.message.clone().unwrap_or_default();
+            events.publish(DeviceEvent::SceneNotification { scene_id: scene.id, run_id, message: message.clone() });
+            if let Some(url) = &step.url {
+                notify_webhook(url, scene, run_id, &message).await?;
+            }
+            Ok(Some(message))
+        }
+        (_, None) => Err(SystemError::APIError(400, 0, format!("Scene step {} requires a device", kind.as_str()))),
+    }
+}
+
+// 알림 내용을 JSON으로 POST (2xx 이외의 응답은 실패)
+async fn notify_webhook(url: &str, scene: &Scene, run_id: i64, message: &str) -> Result<(), SystemError> {
+    let client = reqwest::Client::builder()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .map_err(|e| SystemError::APIError(500, 0, format!("Failed to create HTTP client: {}", e)))?;
+    let res = client.post(url)
+        .json(&json!({ "scene_id": scene.id, "scene": scene.name, "run_id": run_id, "message": message }))
+        .send().await
+        .map_err(|e| SystemError::APIError(502, 0, format!("Notification request failed: {}", e)))?;
+    if !res.status().is_success() {
+        return Err(SystemError::APIError(502, 0, format!("Notification webhook returned {}", res.status().as_u16())));
+    }
+    Ok(())
+}
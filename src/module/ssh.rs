@@ -0,0 +1,158 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rocket::serde::Serialize;
+use rocket::tokio::time::timeout;
+use russh::client;
+use russh::keys::{decode_secret_key, HashAlg, PrivateKeyWithHashAlg, PublicKeyOrCertificate};
+use russh::{ChannelMsg, Disconnect};
+use sqlx::SqlitePool;
+use crate::config::{CryptoConfig, SshConfig};
+use crate::db::device::{Device, DeviceOperations};
+use crate::error::SystemError;
+use crate::module::crypto::decrypt;
+use crate::module::host::resolve_host;
+
+pub const DEFAULT_SSH_PORT: u16 = 22;
+
+pub enum SshAuth {
+    Password(String),
+    // OpenSSH/PEM 형식의 개인키 (암호 없는 키)
+    PrivateKey(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct SshCommandResult {
+    pub device_id: i64,
+    pub command: String,
+    // 종료 코드를 받기 전에 연결이 끊긴 경우(종료 명령 등) null
+    pub exit_code: Option<u32>,
+    pub stdout: String,
+    pub stderr: String,
+    // 접속한 서버의 호스트 키 지문 (SHA256)
+    pub host_key: Option<String>,
+    // 이번 접속에서 처음 신뢰하여 저장한 키인지
+    pub host_key_trusted: bool,
+}
+
+// 서버 호스트 키 확인 (처음 접속시 신뢰, 이후에는 저장된 지문과 일치해야 함)
+struct HostKeyCheck {
+    expected: Option<String>,
+    seen: Arc<Mutex<Option<String>>>,
+}
+
+impl client::Handler for HostKeyCheck {
+    type Error = russh::Error;
+
+    async fn check_server_key(&mut self, key: &PublicKeyOrCertificate) -> Result<bool, Self::Error> {
+        let fingerprint = match key {
+            PublicKeyOrCertificate::PublicKey { key, .. } => key.fingerprint(HashAlg::Sha256),
+            PublicKeyOrCertificate::Certificate(certificate) => certificate.public_key().fingerprint(HashAlg::Sha256),
+        }.to_string();
+        let trusted = self.expected.as_deref().is_none_or(|expected| expected == fingerprint);
+        *self.seen.lock().unwrap() = Some(fingerprint);
+        Ok(trusted)
+    }
+}
+
+// 장치에 설정된 SSH 계정으로 종료 명령 실행
+// 처음 접속한 서버의 호스트 키는 장치에 저장되며, 이후 키가 바뀌면 저장된 키를 초기화할 때까지 거부됨
+pub async fn power_off(pool: &SqlitePool, crypto: &CryptoConfig, config: &SshConfig, device: &Device) -> Result<SshCommandResult, SystemError> {
+    let user = device.ssh_user.as_deref()
+        .ok_or(SystemError::APIError(400, 0, "SSH user is not configured for this device".to_string()))?;
+    let auth = match (&device.ssh_private_key, &device.ssh_password) {
+        (Some(key), _) => SshAuth::PrivateKey(decrypt(crypto, key)?),
+        (None, Some(password)) => SshAuth::Password(decrypt(crypto, password)?),
+        (None, None) => return Err(SystemError::APIError(400, 0, "SSH password or private key is not configured".to_string())),
+    };
+    let ip = resolve_host(&device.ip).await
+        .ok_or(SystemError::APIError(400, 0, "Device has no reachable address for SSH".to_string()))?;
+    let port = device.ssh_port.and_then(|port| u16::try_from(port).ok()).unwrap_or(DEFAULT_SSH_PORT);
+    let command = device.ssh_command.clone().unwrap_or_else(|| config.default_command.clone());
+
+    let mut result = run_command(SocketAddr::new(ip, port), user, auth, device.ssh_host_key.clone(), &command, config).await?;
+    result.device_id = device.id;
+    if result.host_key_trusted && let Some(host_key) = &result.host_key {
+        Device::update_ssh_host_key(pool, device.id, host_key).await?;
+    }
+    Ok(result)
+}
+
+pub async fn run_command(addr: SocketAddr, user: &str, auth: SshAuth, known_host_key: Option<String>, command: &str,
+                         config: &SshConfig) -> Result<SshCommandResult, SystemError> {
+    let seen = Arc::new(Mutex::new(None));
+    let handler = HostKeyCheck { expected: known_host_key.clone(), seen: Arc::clone(&seen) };
+    let ssh_config = Arc::new(client::Config {
+        inactivity_timeout: Some(Duration::from_secs(config.command_timeout_secs.max(1))),
+        ..Default::default()
+    });
+
+    let connect = client::connect(ssh_config, addr, handler);
+    let mut session = match timeout(Duration::from_millis(config.connect_timeout_ms), connect).await {
+        Ok(Ok(session)) => session,
+        Ok(Err(russh::Error::UnknownKey)) => {
+            let seen = seen.lock().unwrap().clone().unwrap_or_default();
+            return Err(SystemError::APIError(409, 0, format!(
+                "SSH host key mismatch for {} (stored {}, received {}). Reset the stored host key if the device was reinstalled.",
+                addr.ip(), known_host_key.unwrap_or_default(), seen)));
+        }
+        Ok(Err(e)) => return Err(SystemError::APIError(502, 0, format!("SSH connection failed: {}", e))),
+        Err(_) => return Err(SystemError::APIError(504, 0, format!("Timed out connecting to {}", addr))),
+    };
+
+    let authenticated = match auth {
+        SshAuth::Password(password) => session.authenticate_password(user, password).await,
+        SshAuth::PrivateKey(key) => {
+            let key = decode_secret_key(&key, None)
+                .map_err(|e| SystemError::APIError(400, 0, format!("Invalid SSH private key: {}", e)))?;
+            let hash_alg = session.best_supported_rsa_hash().await.ok().flatten().flatten();
+            session.authenticate_publickey(user, PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg)).await
+        }
+    }.map_err(|e| SystemError::APIError(502, 0, format!("SSH authentication failed: {}", e)))?;
+    if !authenticated.success() {
+        return Err(SystemError::APIError(401, 0, format!("SSH authentication rejected for user {}", user)));
+    }
+
+    let mut channel = session.channel_open_session().await
+        .map_err(|e| SystemError::APIError(502, 0, format!("Failed to open SSH channel: {}", e)))?;
+    channel.exec(true, command).await
+        .map_err(|e| SystemError::APIError(502, 0, format!("Failed to run command: {}", e)))?;
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut exit_code = None;
+    // 종료 명령은 응답 없이 연결이 끊기는 경우가 많으므로 시간 초과도 정상 종료로 취급
+    let _ = timeout(Duration::from_secs(config.command_timeout_secs), async {
+        while let Some(msg) = channel.wait().await {
+            match msg {
+                ChannelMsg::Data { data } => append_limited(&mut stdout, &data, config.max_output_bytes),
+                ChannelMsg::ExtendedData { data, .. } => append_limited(&mut stderr, &data, config.max_output_bytes),
+                ChannelMsg::ExitStatus { exit_status } => exit_code = Some(exit_status),
+                _ => {}
+            }
+        }
+    }).await;
+    let _ = session.disconnect(Disconnect::ByApplication, "", "en").await;
+
+    let host_key = seen.lock().unwrap().clone();
+    Ok(SshCommandResult {
+        device_id: 0,
+        command: command.to_string(),
+        exit_code,
+        stdout: String::from_utf8_lossy(&stdout).to_string(),
+        stderr: String::from_utf8_lossy(&stderr).to_string(),
+        host_key_trusted: known_host_key.is_none() && host_key.is_some(),
+        host_key,
+    })
+}
+
+fn append_limited(buffer: &mut Vec<u8>, data: &[u8], max: usize) {
+    let remaining = max.saturating_sub(buffer.len());
+    buffer.extend_from_slice(&data[..data.len().min(remaining)]);
+}
+
+// 개인키 형식 검증용
+pub fn is_valid_private_key(key: &str) -> bool {
+    decode_secret_key(key, None).is_ok()
+}
@@ -0,0 +1,139 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::Utc;
+use rocket::serde::Serialize;
+use rocket::tokio::task::spawn_blocking;
+use rocket::tokio::time::timeout;
+use crate::config::WolConfig;
+use crate::error::SystemError;
+use crate::module::ethernet::send_raw_magic_packet;
+use crate::module::magic_packet::send_udp_packet;
+
+// 드라이런 모드에서 보관하는 최대 패킷 수 (초과시 오래된 것부터 삭제)
+const MAX_CAPTURED_PACKETS: usize = 1000;
+
+// 패킷 전송 계층. 실제 소켓 대신 다른 구현(드라이런 등)으로 교체할 수 있도록 분리
+#[async_trait::async_trait]
+pub trait PacketTransport: Send + Sync {
+    async fn send_udp(&self, payload: &[u8], target: SocketAddr, interface: Option<&str>) -> Result<(), SystemError>;
+    async fn send_ethernet(&self, payload: &[u8], interface: &str, vlan_id: Option<u16>) -> Result<(), SystemError>;
+
+    // 기록된 패킷 목록 (패킷을 기록하는 구현에서만 Some)
+    fn captured(&self) -> Option<Vec<CapturedPacket>> {
+        None
+    }
+    fn clear_captured(&self) {}
+}
+
+// Rocket State로 관리되는 전송 계층
+pub type SharedTransport = Arc<dyn PacketTransport>;
+
+// 설정에 따라 전송 계층 생성
+pub fn create_transport(config: &WolConfig) -> SharedTransport {
+    if config.dry_run {
+        println!("WOL dry-run mode enabled. Magic packets will be recorded instead of sent.");
+        Arc::new(DryRunTransport::default())
+    } else {
+        Arc::new(SystemTransport { timeout: Duration::from_millis(config.send_timeout_ms) })
+    }
+}
+
+// 실제 네트워크로 전송
+pub struct SystemTransport {
+    // 패킷 1개 전송에 허용되는 최대 시간
+    pub timeout: Duration,
+}
+
+#[async_trait::async_trait]
+impl PacketTransport for SystemTransport {
+    async fn send_udp(&self, payload: &[u8], target: SocketAddr, interface: Option<&str>) -> Result<(), SystemError> {
+        timeout(self.timeout, send_udp_packet(payload, target, interface)).await
+            .map_err(|_| SystemError::APIError(504, 0, "Timed out sending magic packet".to_string()))?
+    }
+
+    async fn send_ethernet(&self, payload: &[u8], interface: &str, vlan_id: Option<u16>) -> Result<(), SystemError> {
+        // raw 소켓 전송은 블로킹 호출이므로 별도 스레드에서 수행
+        let payload = payload.to_vec();
+        let interface = interface.to_string();
+        let task = spawn_blocking(move || send_raw_magic_packet(&interface, vlan_id, &payload));
+        timeout(self.timeout, task).await
+            .map_err(|_| SystemError::APIError(504, 0, "Timed out sending raw ethernet frame".to_string()))?
+            .map_err(|e| SystemError::APIError(500, 0, format!("Raw ethernet send task failed: {}", e)))?
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct CapturedPacket {
+    pub sent_at: String,
+    // udp 또는 ethernet
+    pub transport: String,
+    // UDP는 목적지 IP, raw 이더넷은 목적지 MAC
+    pub target: String,
+    pub port: Option<u16>,
+    pub interface: Option<String>,
+    pub vlan_id: Option<u16>,
+    pub payload_hex: String,
+}
+
+// 패킷을 전송하지 않고 기록만 하는 드라이런 구현 (CI, 개발 환경용)
+#[derive(Default)]
+pub struct DryRunTransport {
+    packets: Mutex<Vec<CapturedPacket>>,
+}
+
+impl DryRunTransport {
+    fn record(&self, packet: CapturedPacket) {
+        let mut packets = self.packets.lock().unwrap();
+        if packets.len() >= MAX_CAPTURED_PACKETS {
+            packets.remove(0);
+        }
+        packets.push(packet);
+    }
+}
+
+#[async_trait::async_trait]
+impl PacketTransport for DryRunTransport {
+    async fn send_udp(&self, payload: &[u8], target: SocketAddr, interface: Option<&str>) -> Result<(), SystemError> {
+        self.record(CapturedPacket {
+            sent_at: now(),
+            transport: "udp".to_string(),
+            target: target.ip().to_string(),
+            port: Some(target.port()),
+            interface: interface.map(str::to_string),
+            vlan_id: None,
+            payload_hex: to_hex(payload),
+        });
+        Ok(())
+    }
+
+    async fn send_ethernet(&self, payload: &[u8], interface: &str, vlan_id: Option<u16>) -> Result<(), SystemError> {
+        self.record(CapturedPacket {
+            sent_at: now(),
+            transport: "ethernet".to_string(),
+            target: "FF:FF:FF:FF:FF:FF".to_string(),
+            port: None,
+            interface: Some(interface.to_string()),
+            vlan_id,
+            payload_hex: to_hex(payload),
+        });
+        Ok(())
+    }
+
+    fn captured(&self) -> Option<Vec<CapturedPacket>> {
+        Some(self.packets.lock().unwrap().clone())
+    }
+
+    fn clear_captured(&self) {
+        self.packets.lock().unwrap().clear();
+    }
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+}
@@ -0,0 +1,224 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use rocket::serde::{Deserialize, Serialize};
+use crate::config::{CryptoConfig, WolConfig};
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::crypto::decrypt;
+use crate::module::host::with_resolved_ip;
+use crate::module::magic_packet::{create_magic_packet, create_sleep_packet, parse_mac_address, parse_secureon_password,
+                                  send_magic_packet, MagicPacketTarget, PacketDestination, IPV6_ALL_NODES};
+use crate::module::transport::PacketTransport;
+
+// 한 번의 웨이크 요청에서 보낼 수 있는 최대 전송 횟수
+pub const MAX_BURST_COUNT: u32 = 20;
+pub const MAX_BURST_INTERVAL_MS: u64 = 10_000;
+
+// UDP 매직패킷 전송 대상 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeTarget {
+    // 전역 브로드캐스트 (255.255.255.255, IPv6는 ff02::1)
+    Broadcast,
+    // 서브넷 브로드캐스트 (지정한 broadcast 또는 ip/netmask로 계산한 주소, IPv6는 지정한 멀티캐스트 주소)
+    Subnet,
+    // 마지막으로 알려진 장치 IP로 유니캐스트
+    Unicast,
+}
+
+impl WakeTarget {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "broadcast" => Some(WakeTarget::Broadcast),
+            "subnet" => Some(WakeTarget::Subnet),
+            "unicast" => Some(WakeTarget::Unicast),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WakeTarget::Broadcast => "broadcast",
+            WakeTarget::Subnet => "subnet",
+            WakeTarget::Unicast => "unicast",
+        }
+    }
+}
+
+// 장치에 적용되는 전송 정책 (장치 설정 > 전역 설정 순으로 적용)
+#[derive(Debug, Clone)]
+pub struct WakePolicy {
+    pub count: u32,
+    pub interval: Duration,
+    pub targets: Vec<WakeTarget>,
+}
+
+impl WakePolicy {
+    pub fn resolve(device: &Device, config: &WolConfig) -> Result<Self, SystemError> {
+        let count = device.burst_count.map(|c| c as u32).unwrap_or(config.burst_count);
+        let interval_ms = device.burst_interval_ms.map(|i| i as u64).unwrap_or(config.burst_interval_ms);
+
+        let targets = match &device.wake_targets {
+            Some(targets) => parse_targets(targets.split(','))?,
+            None => parse_targets(config.targets.iter().map(String::as_str))?,
+        };
+
+        Ok(Self {
+            count: count.clamp(1, MAX_BURST_COUNT),
+            interval: Duration::from_millis(interval_ms.min(MAX_BURST_INTERVAL_MS)),
+            targets: if targets.is_empty() { vec![WakeTarget::Subnet] } else { targets },
+        })
+    }
+}
+
+fn parse_targets<'a>(values: impl Iterator<Item = &'a str>) -> Result<Vec<WakeTarget>, SystemError> {
+    let mut targets = Vec::new();
+    for value in values.filter(|v| !v.trim().is_empty()) {
+        let target = WakeTarget::parse(value)
+            .ok_or(SystemError::APIError(500, 0, format!("Invalid wake target: {}", value)))?;
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    Ok(targets)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakeAttempt {
+    // 몇 번째 전송 라운드인지 (1부터 시작)
+    pub round: u32,
+    // broadcast, subnet, unicast, ethernet
+    pub target: String,
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakeReport {
+    pub device_id: i64,
+    pub sent: usize,
+    pub failed: usize,
+    pub attempts: Vec<WakeAttempt>,
+}
+
+// 장치 설정과 전송 정책에 따라 매직패킷을 여러 번 전송하고 시도별 결과를 반환
+pub async fn wake(transport: &dyn PacketTransport, crypto: &CryptoConfig, config: &WolConfig, device: &Device)
+    -> Result<WakeReport, SystemError> {
+    // 호스트 이름으로 지정된 장치는 전송 시점에 IP를 조회
+    let device = &with_resolved_ip(device).await;
+    let secureon = match &device.secureon {
+        Some(encrypted) => Some(parse_secureon_password(&decrypt(crypto, encrypted)?)?),
+        None => None,
+    };
+    let packet = create_magic_packet(parse_mac_address(&device.mac)?, secureon.as_deref());
+    send_packets(transport, config, device, &packet).await
+}
+
+// Sleep-on-LAN 에이전트가 설치된 장치에 MAC을 뒤집은 매직패킷을 웨이크와 같은 경로로 전송
+pub async fn sleep(transport: &dyn PacketTransport, config: &WolConfig, device: &Device) -> Result<WakeReport, SystemError> {
+    if !device.sleep_on_lan {
+        return Err(SystemError::APIError(400, 0, "Sleep-on-LAN is not enabled for this device".to_string()));
+    }
+    let device = &with_resolved_ip(device).await;
+    let packet = create_sleep_packet(parse_mac_address(&device.mac)?);
+    send_packets(transport, config, device, &packet).await
+}
+
+// 장치의 전송 정책에 따라 패킷을 여러 번 전송하고 시도별 결과를 반환
+async fn send_packets(transport: &dyn PacketTransport, config: &WolConfig, device: &Device, packet: &[u8])
+    -> Result<WakeReport, SystemError> {
+    let target = MagicPacketTarget::from_device(device)?;
+    let policy = WakePolicy::resolve(device, config)?;
+
+    let mut attempts = Vec::new();
+    for round in 1..=policy.count {
+        if round > 1 && !policy.interval.is_zero() {
+            rocket::tokio::time::sleep(policy.interval).await;
+        }
+
+        if target.transport.uses_udp() {
+            for kind in &policy.targets {
+                let address = match udp_address(*kind, device, &target) {
+                    Ok(address) => address,
+                    Err(e) => {
+                        attempts.push(WakeAttempt {
+                            round, target: kind.as_str().to_string(), address: None, port: Some(target.port),
+                            success: false, error: Some(e.message()),
+                        });
+                        continue;
+                    }
+                };
+                let result = send_magic_packet(transport, packet, PacketDestination::Udp(address), &target).await;
+                attempts.push(WakeAttempt {
+                    round, target: kind.as_str().to_string(), address: Some(address.ip().to_string()), port: Some(address.port()),
+                    success: result.is_ok(), error: result.err().map(|e| e.message()),
+                });
+            }
+        }
+
+        if target.transport.uses_ethernet() {
+            let result = send_magic_packet(transport, packet, PacketDestination::Ethernet, &target).await;
+            attempts.push(WakeAttempt {
+                round, target: "ethernet".to_string(), address: target.interface.clone(), port: None,
+                success: result.is_ok(), error: result.err().map(|e| e.message()),
+            });
+        }
+    }
+
+    let sent = attempts.iter().filter(|a| a.success).count();
+    Ok(WakeReport {
+        device_id: device.id,
+        sent,
+        failed: attempts.len() - sent,
+        attempts,
+    })
+}
+
+fn udp_address(kind: WakeTarget, device: &Device, target: &MagicPacketTarget) -> Result<SocketAddr, SystemError> {
+    let ip = match kind {
+        WakeTarget::Broadcast if target.address.is_ipv6() => IpAddr::V6(IPV6_ALL_NODES),
+        WakeTarget::Broadcast => IpAddr::V4(Ipv4Addr::BROADCAST),
+        WakeTarget::Subnet => target.address,
+        WakeTarget::Unicast => device.ip.parse::<IpAddr>()
+            .map_err(|_| SystemError::APIError(400, 0, "Device has no known IP address for unicast".to_string()))?,
+    };
+    target.socket_addr(ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_uses_config_defaults() {
+        let policy = WakePolicy::resolve(&Device::default(), &WolConfig::default()).unwrap();
+        assert_eq!(policy.count, 3);
+        assert_eq!(policy.interval, Duration::from_millis(100));
+        assert_eq!(policy.targets, vec![WakeTarget::Subnet]);
+    }
+
+    #[test]
+    fn policy_clamps_device_settings() {
+        let config = WolConfig::default();
+        let mut device = Device { burst_count: Some(0), burst_interval_ms: Some(60_000), ..Default::default() };
+        let policy = WakePolicy::resolve(&device, &config).unwrap();
+        assert_eq!(policy.count, 1);
+        assert_eq!(policy.interval, Duration::from_millis(MAX_BURST_INTERVAL_MS));
+
+        device.burst_count = Some(100);
+        assert_eq!(WakePolicy::resolve(&device, &config).unwrap().count, MAX_BURST_COUNT);
+    }
+
+    #[test]
+    fn policy_dedups_targets_and_falls_back_to_subnet() {
+        let config = WolConfig::default();
+        let device = Device { wake_targets: Some("unicast,broadcast,unicast".to_string()), ..Default::default() };
+        assert_eq!(WakePolicy::resolve(&device, &config).unwrap().targets, vec![WakeTarget::Unicast, WakeTarget::Broadcast]);
+
+        let config = WolConfig { targets: vec![], ..Default::default() };
+        assert_eq!(WakePolicy::resolve(&Device::default(), &config).unwrap().targets, vec![WakeTarget::Subnet]);
+    }
+}
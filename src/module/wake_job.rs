@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use rocket::serde::Serialize;
+use uuid::Uuid;
+use crate::db::dependency::{DependencyOperations, DeviceDependency};
+use crate::db::device::Device;
+use crate::error::SystemError;
+use crate::module::events::{DeviceEvent, EventBus};
+use crate::module::host::resolve_host;
+use crate::module::monitor::reachability_ports;
+use crate::module::power::{driver_for, PowerReport, PowerResources};
+use crate::module::probe::is_reachable;
+use crate::module::wake_plan::{dependencies_for, power_on_step, resolve_plan, wake_dependencies, WakeStep};
+
+// 완료된 작업을 보관하는 시간
+const JOB_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum WakeJobStatus {
+    // 의존 장치를 켜고 응답 대기 중, 또는 켜기 요청 중
+    Waking,
+    // 장치 응답 대기 중
+    Verifying,
+    Online,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakeJob {
+    pub id: String,
+    pub device_id: i64,
+    pub status: WakeJobStatus,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    // 작업 시작부터 경과한 시간 (완료된 작업은 완료 시점까지)
+    pub elapsed_secs: u64,
+    // 먼저 켠 의존 장치별 결과
+    pub dependencies: Vec<WakeStep>,
+    // 장치의 전원 드라이버로 켠 결과
+    pub report: Option<PowerReport>,
+    pub message: Option<String>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl WakeJob {
+    fn is_finished(&self) -> bool {
+        matches!(self.status, WakeJobStatus::Online | WakeJobStatus::Failed)
+    }
+}
+
+// 진행중/완료된 웨이크 작업 목록 (Rocket State로 관리)
+pub struct WakeJobs {
+    jobs: Mutex<HashMap<String, WakeJob>>,
+    events: EventBus,
+}
+
+impl WakeJobs {
+    // 작업 상태가 바뀔 때마다 이벤트 발행
+    pub fn new(events: EventBus) -> Self {
+        Self { jobs: Mutex::default(), events }
+    }
+
+    fn create(&self, device_id: i64) -> WakeJob {
+        let job = WakeJob {
+            id: Uuid::new_v4().to_string(),
+            device_id,
+            status: WakeJobStatus::Waking,
+            started_at: now(),
+            finished_at: None,
+            elapsed_secs: 0,
+            dependencies: Vec::new(),
+            report: None,
+            message: None,
+            started: Instant::now(),
+        };
+
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|_, job| !job.is_finished() || job.started.elapsed() < JOB_RETENTION);
+        jobs.insert(job.id.clone(), job.clone());
+        job
+    }
+
+    pub fn get(&self, id: &str) -> Option<WakeJob> {
+        let mut job = self.jobs.lock().unwrap().get(id).cloned()?;
+        if !job.is_finished() {
+            job.elapsed_secs = job.started.elapsed().as_secs();
+        }
+        Some(job)
+    }
+
+    fn update(&self, id: &str, f: impl FnOnce(&mut WakeJob)) {
+        let updated = self.jobs.lock().unwrap().get_mut(id).map(|job| {
+            f(job);
+            job.clone()
+        });
+        if let Some(job) = updated {
+            self.events.publish(DeviceEvent::WakeJob { job: Box::new(job) });
+        }
+    }
+
+    // 단계별 전원 드라이버 결과를 wake/power 이벤트로 전달
+    fn publish_report(&self, step: &WakeStep) {
+        if let Some(report) = &step.report {
+            self.events.publish(report.event());
+        }
+    }
+
+    fn finish(&self, id: &str, status: WakeJobStatus, message: String) {
+        self.update(id, |job| {
+            job.status = status;
+            job.finished_at = Some(now());
+            job.elapsed_secs = job.started.elapsed().as_secs();
+            job.message = Some(message);
+        });
+    }
+}
+
+// 의존 장치를 먼저 켠 뒤 장치를 켜고 응답할 때까지 확인하는 작업을 백그라운드로 시작
+// 켜기는 장치별 전원 드라이버로, 응답 확인은 상태 모니터링과 같은 포트(tcp_fallback_ports와 verify_port)로 수행
+pub fn start_wake_job(jobs: Arc<WakeJobs>, resources: PowerResources, device: Device) -> WakeJob {
+    let job = jobs.create(device.id);
+    let job_id = job.id.clone();
+
+    rocket::tokio::spawn(async move {
+        let ctx = resources.context();
+        let config = &resources.wol;
+        let dependencies = match dependencies_for(&resources.pool, device.id).await {
+            Ok(dependencies) => dependencies,
+            Err(e) => return jobs.finish(&job_id, WakeJobStatus::Failed, e.message()),
+        };
+        let result = wake_dependencies(&ctx, &dependencies, &mut HashMap::new(), |step| {
+            jobs.publish_report(step);
+            jobs.update(&job_id, |job| job.dependencies.push(step.clone()));
+        }).await;
+        if let Err(step) = result {
+            return jobs.finish(&job_id, WakeJobStatus::Failed, step.blocked_reason());
+        }
+
+        let step = power_on_step(&ctx, &device, false).await;
+        jobs.publish_report(&step);
+        let failed = step.status.blocks_dependents().then(|| step.error.clone().unwrap_or_default());
+        jobs.update(&job_id, |job| {
+            job.status = WakeJobStatus::Verifying;
+            job.report = step.report;
+        });
+        if let Some(error) = failed {
+            return jobs.finish(&job_id, WakeJobStatus::Failed, error);
+        }
+
+        let ip = match resolve_host(&device.ip).await {
+            Some(ip) => ip,
+            None => return jobs.finish(&job_id, WakeJobStatus::Failed, "Device has no IP address to verify".to_string()),
+        };
+        let ports = reachability_ports(&device, &resources.monitor);
+        let probe_timeout = Duration::from_millis(config.probe_timeout_ms);
+        let interval = Duration::from_secs(config.verify_interval_secs.max(1));
+        let deadline = Instant::now() + Duration::from_secs(config.verify_timeout_secs);
+
+        loop {
+            if is_reachable(ip, &ports, probe_timeout).await {
+                let elapsed = jobs.get(&job_id).map(|job| job.elapsed_secs).unwrap_or(0);
+                return jobs.finish(&job_id, WakeJobStatus::Online, format!("Online after {}s", elapsed));
+            }
+            if Instant::now() + interval > deadline {
+                return jobs.finish(&job_id, WakeJobStatus::Failed,
+                                   format!("Device did not come up within {}s", config.verify_timeout_secs));
+            }
+            rocket::tokio::time::sleep(interval).await;
+        }
+    });
+
+    job
+}
+
+// /device/wake/<id>, WebSocket wake 명령의 응답
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakeStart {
+    pub device_id: i64,
+    // 켜는 순서대로 나열된 장치 (의존 장치가 먼저, 요청한 장치가 마지막)
+    pub plan: Vec<i64>,
+    // 의존 장치가 없어 바로 켠 경우의 전원 드라이버 결과
+    pub report: Option<PowerReport>,
+    // 의존 장치가 있어 백그라운드로 시작한 작업 (진행 상황은 /wake-job/<job_id> 또는 wake_job 이벤트)
+    pub job: Option<WakeJob>,
+}
+
+// 장치 웨이크 요청 처리
+// 의존 장치는 응답을 기다려야 하므로(장치마다 최대 verify_timeout_secs) 요청을 붙잡지 않도록 웨이크 작업으로 넘기고,
+// 의존 장치가 없으면 장치의 전원 드라이버로 바로 켬
+pub async fn start_wake(jobs: Arc<WakeJobs>, resources: PowerResources, device: Device) -> Result<WakeStart, SystemError> {
+    let dependencies = DeviceDependency::get_all(&resources.pool).await?;
+    let plan = resolve_plan(&dependencies, device.id)?;
+    if plan.len() > 1 {
+        let device_id = device.id;
+        let job = start_wake_job(jobs, resources, device);
+        return Ok(WakeStart { device_id, plan, report: None, job: Some(job) });
+    }
+
+    let report = driver_for(&resources.crypto, &device)?.power_on(&resources.context(), &device).await?;
+    jobs.events.publish(report.event());
+    Ok(WakeStart { device_id: device.id, plan, report: Some(report), job: None })
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
@@ -0,0 +1,358 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use rocket::serde::Serialize;
+use sqlx::SqlitePool;
+use crate::config::WolConfig;
+use crate::db::dependency::{DependencyOperations, DeviceDependency};
+use crate::db::device::{Device, DeviceOperations};
+use crate::error::SystemError;
+use crate::module::host::resolve_host;
+use crate::module::monitor::reachability_ports;
+use crate::module::power::{driver_for, PowerContext, PowerReport};
+use crate::module::probe::is_reachable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum WakeStepStatus {
+    // 웨이크 전에 이미 응답하고 있어 켜기 요청을 보내지 않음
+    AlreadyOnline,
+    // 웨이크 후 응답 확인
+    Online,
+    // 제한 시간 안에 응답하지 않음
+    Timeout,
+    // 전원 드라이버로 켜기 요청 (응답 확인을 하지 않는 대상 장치 또는 IP가 없는 장치)
+    Sent,
+    Failed,
+}
+
+impl WakeStepStatus {
+    // 의존 장치가 이 상태로 끝나면 의존하는 장치는 켜지 않음 (웨이크 작업, 그룹 웨이크, 씬 공통)
+    pub fn blocks_dependents(self) -> bool {
+        matches!(self, WakeStepStatus::Failed | WakeStepStatus::Timeout)
+    }
+}
+
+// 웨이크 계획의 단계별 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakeStep {
+    pub device_id: i64,
+    pub name: String,
+    // 요청한 장치가 아닌 의존 장치인지 여부
+    pub dependency: bool,
+    pub status: WakeStepStatus,
+    // 전원 드라이버의 켜기 결과 (WoL 드라이버는 detail에 매직패킷 전송 결과)
+    pub report: Option<PowerReport>,
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+impl WakeStep {
+    // 이 의존 장치 때문에 켜지 않은 장치에 기록하는 사유
+    pub fn blocked_reason(&self) -> String {
+        match &self.error {
+            Some(error) => format!("Dependency {} did not come up: {}", self.name, error),
+            None => format!("Dependency {} did not come up", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct WakePlanReport {
+    pub device_id: i64,
+    // 켜는 순서대로 나열된 장치 (의존 장치가 먼저, 요청한 장치가 마지막)
+    pub plan: Vec<i64>,
+    pub steps: Vec<WakeStep>,
+}
+
+fn dependency_map(dependencies: &[DeviceDependency]) -> HashMap<i64, Vec<i64>> {
+    let mut map: HashMap<i64, Vec<i64>> = HashMap::new();
+    for dependency in dependencies {
+        map.entry(dependency.device_id).or_default().push(dependency.depends_on);
+    }
+    map
+}
+
+// device_id의 의존 대상을 depends_on으로 바꿨을 때 생기는 순환 경로 (device_id에서 시작하여 device_id로 끝남)
+pub fn find_cycle(dependencies: &[DeviceDependency], device_id: i64, depends_on: &[i64]) -> Option<Vec<i64>> {
+    let mut map = dependency_map(dependencies);
+    map.insert(device_id, depends_on.to_vec());
+
+    fn visit(map: &HashMap<i64, Vec<i64>>, target: i64, current: i64, path: &mut Vec<i64>, visited: &mut HashSet<i64>) -> bool {
+        for &next in map.get(&current).into_iter().flatten() {
+            path.push(next);
+            if next == target || (visited.insert(next) && visit(map, target, next, path, visited)) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut path = vec![device_id];
+    visit(&map, device_id, device_id, &mut path, &mut HashSet::new()).then_some(path)
+}
+
+// 의존 관계를 따라 켜야 하는 순서를 계산 (의존 장치가 먼저, device_id가 마지막)
+pub fn resolve_plan(dependencies: &[DeviceDependency], device_id: i64) -> Result<Vec<i64>, SystemError> {
+    let map = dependency_map(dependencies);
+
+    fn visit(map: &HashMap<i64, Vec<i64>>, current: i64, visiting: &mut Vec<i64>, plan: &mut Vec<i64>) -> Result<(), SystemError> {
+        if plan.contains(&current) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|&id| id == current) {
+            let cycle: Vec<String> = visiting[start..].iter().chain([&current]).map(i64::to_string).collect();
+            return Err(SystemError::APIError(409, 0, format!("Dependency cycle detected: {}", cycle.join(" -> "))));
+        }
+        visiting.push(current);
+        for &next in map.get(&current).into_iter().flatten() {
+            visit(map, next, visiting, plan)?;
+        }
+        visiting.pop();
+        plan.push(current);
+        Ok(())
+    }
+
+    let mut plan = Vec::new();
+    visit(&map, device_id, &mut Vec::new(), &mut plan)?;
+    Ok(plan)
+}
+
+// 장치보다 먼저 켜야 하는 의존 장치 목록 (켜는 순서대로)
+pub async fn dependencies_for(pool: &SqlitePool, device_id: i64) -> Result<Vec<Device>, SystemError> {
+    let dependencies = DeviceDependency::get_all(pool).await?;
+    let plan = resolve_plan(&dependencies, device_id)?;
+    let mut devices = Vec::with_capacity(plan.len());
+    for id in plan.into_iter().filter(|&id| id != device_id) {
+        if let Some(device) = Device::get(pool, id).await? {
+            devices.push(device);
+        }
+    }
+    Ok(devices)
+}
+
+// 장치의 전원 드라이버로 켜기 요청 (응답은 확인하지 않음)
+pub async fn power_on_step(ctx: &PowerContext<'_>, device: &Device, dependency: bool) -> WakeStep {
+    let started = Instant::now();
+    let result = match driver_for(ctx.crypto, device) {
+        Ok(driver) => driver.power_on(ctx, device).await,
+        Err(e) => Err(e),
+    };
+    let (status, report, error) = match result {
+        Ok(report) => match report.failure() {
+            Some(error) => (WakeStepStatus::Failed, Some(report), Some(error)),
+            None => (WakeStepStatus::Sent, Some(report), None),
+        },
+        Err(e) => (WakeStepStatus::Failed, None, Some(e.message())),
+    };
+    WakeStep {
+        device_id: device.id,
+        name: device.name.clone(),
+        dependency,
+        status,
+        report,
+        error,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+// 의존 장치를 켜고 응답할 때까지(또는 verify_timeout_secs가 지날 때까지) 대기
+// 응답 확인은 상태 모니터링과 같은 포트(tcp_fallback_ports와 verify_port)로 하며, 이미 응답하고 있는 장치에는 켜기 요청을 보내지 않음
+pub async fn wake_dependency(ctx: &PowerContext<'_>, device: &Device) -> WakeStep {
+    let started = Instant::now();
+    let config = ctx.wol;
+    let ip = resolve_host(&device.ip).await;
+    let ports = reachability_ports(device, ctx.monitor);
+    let probe_timeout = Duration::from_millis(config.probe_timeout_ms);
+
+    if let Some(ip) = ip
+        && is_reachable(ip, &ports, probe_timeout).await {
+        return WakeStep {
+            device_id: device.id,
+            name: device.name.clone(),
+            dependency: true,
+            status: WakeStepStatus::AlreadyOnline,
+            report: None,
+            error: None,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+    }
+    let mut step = power_on_step(ctx, device, true).await;
+    if step.status == WakeStepStatus::Sent {
+        match ip {
+            Some(ip) if wait_until_online(ip, &ports, config, Duration::from_secs(config.verify_timeout_secs)).await => {
+                step.status = WakeStepStatus::Online;
+            }
+            Some(_) => {
+                step.status = WakeStepStatus::Timeout;
+                step.error = Some(format!("Device did not come up within {}s", config.verify_timeout_secs));
+            }
+            None => step.error = Some("Device has no IP address to verify".to_string()),
+        }
+    }
+    step.elapsed_ms = started.elapsed().as_millis() as u64;
+    step
+}
+
+// 의존 장치를 순서대로 켜고 확인하며 단계마다 on_step 호출
+// 의존 장치가 실패하거나 응답하지 않으면 나머지 의존 장치를 켜지 않고 그 단계를 Err로 반환 (의존하는 장치도 켜지 않아야 함)
+// woken에 있는 장치는 다시 켜지 않고 기록된 결과를 사용 (그룹 웨이크에서 먼저 켠 구성원 등)
+pub async fn wake_dependencies(ctx: &PowerContext<'_>, dependencies: &[Device], woken: &mut HashMap<i64, WakeStep>,
+                               mut on_step: impl FnMut(&WakeStep)) -> Result<(), WakeStep> {
+    for dependency in dependencies {
+        let step = match woken.get(&dependency.id) {
+            Some(step) => step.clone(),
+            None => {
+                let step = wake_dependency(ctx, dependency).await;
+                on_step(&step);
+                woken.insert(dependency.id, step.clone());
+                step
+            }
+        };
+        if step.status.blocks_dependents() {
+            return Err(step);
+        }
+    }
+    Ok(())
+}
+
+// verify_interval_secs마다 응답을 확인하며 timeout 동안 대기 (응답하면 true)
+pub async fn wait_until_online(ip: IpAddr, ports: &[u16], config: &WolConfig, timeout: Duration) -> bool {
+    let probe_timeout = Duration::from_millis(config.probe_timeout_ms);
+    let interval = Duration::from_secs(config.verify_interval_secs.max(1));
+    let deadline = Instant::now() + timeout;
+    loop {
+        if is_reachable(ip, ports, probe_timeout).await {
+            return true;
+        }
+        if Instant::now() + interval > deadline {
+            return false;
+        }
+        rocket::tokio::time::sleep(interval).await;
+    }
+}
+
+// 의존 장치를 순서대로 켜고 확인한 뒤 요청한 장치를 켬 (모두 장치별 전원 드라이버 사용)
+// 의존 장치가 실패하거나 응답하지 않으면 요청한 장치는 켜지 않음 (steps의 마지막이 그 의존 장치)
+// 의존 장치마다 최대 verify_timeout_secs를 기다리므로 백그라운드 작업(씬 등)에서만 사용
+pub async fn wake_with_dependencies(ctx: &PowerContext<'_>, device: &Device) -> Result<WakePlanReport, SystemError> {
+    let dependencies = dependencies_for(ctx.pool, device.id).await?;
+    let mut plan: Vec<i64> = dependencies.iter().map(|dependency| dependency.id).collect();
+    plan.push(device.id);
+
+    let mut steps = Vec::with_capacity(plan.len());
+    let result = wake_dependencies(ctx, &dependencies, &mut HashMap::new(), |step| steps.push(step.clone())).await;
+    if result.is_ok() {
+        steps.push(power_on_step(ctx, device, false).await);
+    }
+
+    Ok(WakePlanReport { device_id: device.id, plan, steps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(device_id: i64, depends_on: i64) -> DeviceDependency {
+        DeviceDependency { device_id, depends_on }
+    }
+
+    #[test]
+    fn find_cycle_detects_self_loop() {
+        assert_eq!(find_cycle(&[], 1, &[1]), Some(vec![1, 1]));
+    }
+
+    #[test]
+    fn find_cycle_detects_three_node_cycle() {
+        // 1 -> 2 -> 3에 3 -> 1을 추가
+        let dependencies = [dependency(1, 2), dependency(2, 3)];
+        assert_eq!(find_cycle(&dependencies, 3, &[1]), Some(vec![3, 1, 2, 3]));
+        assert_eq!(find_cycle(&dependencies, 3, &[4]), None);
+    }
+
+    #[test]
+    fn find_cycle_allows_diamond() {
+        // 1 -> 2, 1 -> 3, 2 -> 4, 3 -> 4
+        let dependencies = [dependency(2, 4), dependency(3, 4)];
+        assert_eq!(find_cycle(&dependencies, 1, &[2, 3]), None);
+    }
+
+    #[test]
+    fn resolve_plan_rejects_self_loop() {
+        assert!(resolve_plan(&[dependency(1, 1)], 1).is_err());
+    }
+
+    #[test]
+    fn resolve_plan_rejects_three_node_cycle() {
+        let dependencies = [dependency(1, 2), dependency(2, 3), dependency(3, 1)];
+        for device_id in [1, 2, 3] {
+            assert!(resolve_plan(&dependencies, device_id).is_err());
+        }
+    }
+
+    #[test]
+    fn resolve_plan_orders_diamond_once() {
+        let dependencies = [dependency(1, 2), dependency(1, 3), dependency(2, 4), dependency(3, 4)];
+        assert_eq!(resolve_plan(&dependencies, 1).unwrap(), vec![4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn resolve_plan_without_dependencies() {
+        assert_eq!(resolve_plan(&[dependency(2, 3)], 1).unwrap(), vec![1]);
+    }
+
+    fn step(device_id: i64, status: WakeStepStatus, error: Option<&str>) -> WakeStep {
+        WakeStep {
+            device_id,
+            name: format!("device-{}", device_id),
+            dependency: true,
+            status,
+            report: None,
+            error: error.map(str::to_string),
+            elapsed_ms: 0,
+        }
+    }
+
+    #[test]
+    fn failed_or_timed_out_dependency_blocks_dependents() {
+        assert!(WakeStepStatus::Failed.blocks_dependents());
+        assert!(WakeStepStatus::Timeout.blocks_dependents());
+        assert!(!WakeStepStatus::AlreadyOnline.blocks_dependents());
+        assert!(!WakeStepStatus::Online.blocks_dependents());
+        assert!(!WakeStepStatus::Sent.blocks_dependents());
+        assert_eq!(step(2, WakeStepStatus::Timeout, Some("Device did not come up within 180s")).blocked_reason(),
+                   "Dependency device-2 did not come up: Device did not come up within 180s");
+    }
+
+    #[rocket::async_test]
+    async fn wake_dependencies_stops_at_blocking_dependency() {
+        use crate::config::{CryptoConfig, IpmiConfig, MonitorConfig, SshConfig};
+        use crate::module::transport::{DryRunTransport, PacketTransport};
+
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+        let transport = DryRunTransport::default();
+        let (crypto, wol, ssh) = (CryptoConfig::default(), WolConfig::default(), SshConfig::default());
+        let (monitor, ipmi) = (MonitorConfig::default(), IpmiConfig::default());
+        let ctx = PowerContext { pool: &pool, transport: &transport, crypto: &crypto, wol: &wol, ssh: &ssh, monitor: &monitor, ipmi: &ipmi };
+        let dependencies: Vec<Device> = [1, 2, 3].into_iter().map(|id| Device { id, ..Default::default() }).collect();
+
+        // 먼저 켠 장치의 결과를 그대로 사용하므로 2번에서 멈추고 3번은 켜지 않음
+        let mut woken = HashMap::from([
+            (1, step(1, WakeStepStatus::Online, None)),
+            (2, step(2, WakeStepStatus::Timeout, Some("Device did not come up within 180s"))),
+        ]);
+        let mut woke = Vec::new();
+        let blocked = wake_dependencies(&ctx, &dependencies, &mut woken, |step| woke.push(step.device_id)).await;
+        assert_eq!(blocked.unwrap_err().device_id, 2);
+        assert!(woke.is_empty());
+        assert!(!woken.contains_key(&3));
+        assert_eq!(transport.captured().map(|packets| packets.len()), Some(0));
+
+        woken.insert(2, step(2, WakeStepStatus::AlreadyOnline, None));
+        assert!(wake_dependencies(&ctx, &dependencies[..2], &mut woken, |step| woke.push(step.device_id)).await.is_ok());
+        assert!(woke.is_empty());
+    }
+}